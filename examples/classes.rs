@@ -11,10 +11,14 @@ xrender!(MyList, <ul>
   <li>{{ self.my_string }}</li>
   <li>{{ self.my_int }}</li>
   {{ for i in self.children.iter() {{
-    {{ if let MyListChildren::MyItem(my_item) = i {{
-      <li>MyItem: {{ my_item.my_bool }}</li>
-    }} else if let MyListChildren::MyOtherItem(my_other_item) = i {{
-      <li>MyOtherItem: {{ my_other_item.my_char }}</li>
+    {{ match i {{
+      MyListChildren::MyItem(my_item) => {{
+        <li>MyItem: {{ my_item.my_bool }}</li>
+      }},
+      MyListChildren::MyOtherItem(my_other_item) => {{
+        <li>MyOtherItem: {{ my_other_item.my_char }}</li>
+      }},
+      MyListChildren::MyPredefinedType(_) => {{}},
     }} }}
   }} }}
 </ul>);
@@ -0,0 +1,3 @@
+pub use crate::token_as_literal::TokenAsLiteral;
+pub use crate::emit::{Emitter,Mode};
+pub use crate::dashed_name::DashedName;
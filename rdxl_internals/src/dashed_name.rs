@@ -0,0 +1,83 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::{Span, Spacing, TokenTree};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::ext::IdentExt;
+use syn::{Ident, Token};
+
+/// A name such as data-id or my-widget
+///
+/// The Rust lexer splits a dashed name into identifiers and dashes, so they are joined
+/// back together when each piece starts where the previous piece ends.
+pub struct DashedName {
+   pub name: String,
+   pub span: Span,
+   /// The span of the last piece, since joining spans fails on stable
+   pub end: Span,
+}
+
+fn adjacent(a: Span, b: Span) -> bool {
+   a.end() == b.start()
+}
+
+impl Parse for DashedName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let first = input.call(Ident::parse_any)?;
+        let mut name = first.to_string();
+        let mut end = first.span();
+
+        loop {
+           let joined = input.cursor().punct().and_then(|(dash,rest)| {
+              if dash.as_char() != '-' || !adjacent(end, dash.span()) { return None; }
+              match rest.token_tree() {
+                 Some((TokenTree::Ident(i),_)) if adjacent(dash.span(), i.span()) => { Some(i.to_string()) }
+                 Some((TokenTree::Literal(l),_)) if adjacent(dash.span(), l.span()) &&
+                    l.to_string().chars().all(|c| c.is_ascii_alphanumeric()) => { Some(l.to_string()) }
+                 _ => { None }
+              }
+           });
+           match joined {
+              Some(part) => {
+                 let _dash: Token![-] = input.parse()?;
+                 let part_span = input.step(|cursor| {
+                    let (tt, rest) = cursor.token_tree().expect("dashed name part");
+                    Ok((tt.span(), rest))
+                 })?;
+                 name.push('-');
+                 name.push_str(&part);
+                 end = part_span;
+              },
+              None => { break; }
+           }
+        }
+
+        Ok(DashedName {
+           name: name,
+           span: first.span().join(end).unwrap_or(first.span()),
+           end: end,
+        })
+    }
+}
+
+impl DashedName {
+    /// Parses a name that may have a namespace prefix, such as xlink:href or atom:link
+    pub fn parse_prefixed(input: ParseStream) -> Result<Self> {
+        let prefix: DashedName = input.parse()?;
+        let prefixed = input.cursor().punct().map(|(colon,rest)| {
+           colon.as_char() == ':' && colon.spacing() == Spacing::Alone && adjacent(prefix.end, colon.span()) &&
+           matches!(rest.token_tree(), Some((TokenTree::Ident(i),_)) if adjacent(colon.span(), i.span()))
+        }).unwrap_or(false);
+        if !prefixed { return Ok(prefix); }
+
+        let _colon: Token![:] = input.parse()?;
+        let local: DashedName = input.parse()?;
+        Ok(DashedName {
+           name: format!("{}:{}", prefix.name, local.name),
+           span: prefix.span.join(local.end).unwrap_or(prefix.span),
+           end: local.end,
+        })
+    }
+}
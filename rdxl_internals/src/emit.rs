@@ -0,0 +1,163 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{ToTokens, quote_spanned};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+
+/// The output format of a template
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+   /// xhtml with breaking spaces between inline content
+   Xhtml,
+   /// xhtml without breaking spaces
+   Xtext,
+   /// xhtml with only the breaking spaces that can change how it is displayed,
+   /// and static attributes in their shortest form
+   Minify,
+   /// html5 with breaking spaces, where void elements have no closing slash
+   Html5,
+   /// xml with breaking spaces, where every empty element closes itself
+   Xml,
+}
+
+enum Op {
+   S(String, Span),
+   C(TokenStream),
+}
+
+/// Collects the static text and code that render a template into `stream`
+///
+/// Adjacent static text is merged when it is pushed, so each run of tags, literal text and
+/// breaking spaces becomes a single push_str in the generated code.
+pub struct Emitter {
+   mode: Mode,
+   //whether the markup is the value of an attribute
+   attr: bool,
+   //whether adjacent static text is merged, which is only turned off to measure what it saves
+   merge: bool,
+   ops: Vec<Op>,
+   static_len: usize,
+   layout: Option<Ident>,
+   blocks: Vec<(Ident, TokenStream)>,
+}
+
+impl Emitter {
+   pub fn new(mode: Mode) -> Emitter {
+      Emitter {
+         mode: mode,
+         attr: false,
+         merge: true,
+         ops: Vec::new(),
+         static_len: 0,
+         layout: None,
+         blocks: Vec::new(),
+      }
+   }
+   /// Emits each piece of static text with its own push_str, as templates were before
+   pub fn unmerged(mode: Mode) -> Emitter {
+      let mut e = Emitter::new(mode);
+      e.merge = false;
+      e
+   }
+   /// Emits the markup of a layout, whose blocks become methods of the layout trait
+   pub fn layout(mode: Mode, layout: Ident) -> Emitter {
+      let mut e = Emitter::new(mode);
+      e.layout = Some(layout);
+      e
+   }
+   /// The trait of the layout being emitted, if any
+   pub fn layout_trait(&self) -> Option<&Ident> {
+      self.layout.as_ref()
+   }
+   /// Defines the default body of a block, or returns false if the block is already defined
+   pub fn define_block(&mut self, name: Ident, body: TokenStream) -> bool {
+      if self.blocks.iter().any(|(b,_)| *b == name) { return false; }
+      self.blocks.push((name, body));
+      true
+   }
+   /// The blocks of a layout, in the order they were defined
+   pub fn blocks(&self) -> &[(Ident, TokenStream)] {
+      &self.blocks
+   }
+   pub fn mode(&self) -> Mode {
+      self.mode
+   }
+   /// The method that writes an interpolated value as text in this mode
+   pub fn write_text(&self, span: Span) -> Ident {
+      let write = match (self.mode, self.attr) {
+         (Mode::Xml, true) => { "write_xml_attr" },
+         (Mode::Xml, false) => { "write_xml" },
+         _ => { "write_text" },
+      };
+      Ident::new(write, span)
+   }
+   /// The length of all static text in the template, including the bodies of statements
+   pub fn capacity(&self) -> usize {
+      self.static_len
+   }
+   pub fn push_str(&mut self, s: &str, span: Span) {
+      if s.len() == 0 { return; }
+      self.static_len += s.len();
+      match self.ops.last_mut() {
+         Some(Op::S(prev,_)) if self.merge => { prev.push_str(s); },
+         _ => { self.ops.push(Op::S(s.to_string(), span)); },
+      }
+   }
+   /// Pushes static text content, which xml mode escapes when it is rendered
+   pub fn push_text(&mut self, s: &str, span: Span) {
+      if self.mode == Mode::Xml {
+         if s.len() == 0 { return; }
+         self.static_len += s.len();
+         let escape = Ident::new(if self.attr { "xml_attribute" } else { "xml_text" }, span);
+         let l = Literal::string(s);
+         self.push_code(quote_spanned!{span=>
+            let _ = ::rdxl::escape::#escape(&mut stream, #l);
+         });
+      } else {
+         self.push_str(s, span);
+      }
+   }
+   /// Pushes a breaking space, which is not rendered in xtext mode
+   pub fn push_space(&mut self, span: Span) {
+      if self.mode != Mode::Xtext {
+         self.push_str(" ", span);
+      }
+   }
+   pub fn push_code(&mut self, code: TokenStream) {
+      self.ops.push(Op::C(code));
+   }
+   /// Emits the value of an attribute, whose text xml mode escapes as an attribute value
+   pub fn attribute<F: FnOnce(&mut Emitter)>(&mut self, f: F) {
+      let attr = std::mem::replace(&mut self.attr, true);
+      f(self);
+      self.attr = attr;
+   }
+   /// Emits a block of markup with the same mode, for use in the body of a statement
+   pub fn nested<F: FnOnce(&mut Emitter)>(&mut self, f: F) -> TokenStream {
+      let mut inner = Emitter::new(self.mode);
+      inner.attr = self.attr;
+      inner.merge = self.merge;
+      inner.layout = self.layout.clone();
+      inner.blocks = std::mem::take(&mut self.blocks);
+      f(&mut inner);
+      self.static_len += inner.static_len;
+      self.blocks = std::mem::take(&mut inner.blocks);
+      inner.into_token_stream()
+   }
+}
+
+impl ToTokens for Emitter {
+   fn to_tokens(&self, tokens: &mut TokenStream) {
+      for op in self.ops.iter() {
+         match op {
+            Op::S(s,span) => {
+               let l = Literal::string(s);
+               (quote_spanned!{span.clone()=> stream.push_str(#l); }).to_tokens(tokens);
+            }
+            Op::C(c) => { c.to_tokens(tokens); }
+         }
+      }
+   }
+}
@@ -0,0 +1,25 @@
+#![allow(clippy::redundant_field_names, clippy::clone_on_copy, clippy::len_zero,
+         clippy::cmp_owned, clippy::large_enum_variant, clippy::nonminimal_bool)]
+
+mod token_as_literal;
+mod emit;
+mod dashed_name;
+mod bracketed_expr;
+mod xhtml_class_child;
+mod xhtml_slot;
+mod xhtml_fragment;
+mod xhtml_display_expr;
+mod xhtml_class_attr;
+mod xhtml_expr;
+mod xhtml_attr;
+mod xhtml_token_list;
+mod xhtml_style;
+mod xhtml_class;
+mod xhtml_tag;
+mod xhtml_crumb;
+mod xhtml_str;
+mod xml_instruction;
+mod xml_cdata;
+mod xhtml_comment;
+pub mod core;
+pub mod xhtml;
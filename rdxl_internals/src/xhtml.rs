@@ -0,0 +1,110 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::{Span, LineColumn};
+use syn::parse::{Parse, ParseStream, Result};
+
+use crate::core::{Emitter,Mode};
+
+pub use crate::bracketed_expr::BracketedExpr;
+pub use crate::xhtml_display_expr::XhtmlDisplayExpr;
+pub use crate::xhtml_expr::XhtmlExpr;
+pub use crate::xhtml_class_attr::XhtmlClassAttr;
+pub use crate::xhtml_attr::XhtmlAttr;
+pub use crate::xhtml_token_list::{XhtmlTokenList,XhtmlToken,XhtmlTokenValue};
+pub use crate::xhtml_style::{XhtmlStyleList,XhtmlStyleEntry};
+pub use crate::xhtml_class_child::XhtmlClassChild;
+pub use crate::xhtml_slot::XhtmlSlot;
+pub use crate::xhtml_fragment::XhtmlFragment;
+pub use crate::xhtml_class::XhtmlClass;
+pub use crate::xhtml_tag::XhtmlTag;
+pub use crate::xhtml_crumb::XhtmlCrumb;
+pub use crate::xhtml_str::{XhtmlStr,XhtmlStrError};
+pub use crate::xml_instruction::XmlInstruction;
+pub use crate::xml_cdata::{XmlCdata,XmlCdataPart};
+pub use crate::xhtml_comment::{XhtmlComment,XhtmlCommentPart};
+
+pub struct Xhtml {
+    pub crumbs: Vec<XhtmlCrumb>
+}
+
+impl Xhtml {
+    pub fn span(&self) -> Span {
+       if self.crumbs.len() > 0 {
+          let mut span = self.crumbs[0].span();
+          for c in self.crumbs[1..].iter() {
+             span = span.join(c.span()).unwrap_or(c.span());
+          }
+          span
+       } else {
+          Span::call_site()
+       }
+    }
+    pub fn start(&self) -> LineColumn {
+       //Span::join may fail partway through the crumbs, so compare the ends directly
+       self.crumbs.first().map(|c| c.start()).unwrap_or(LineColumn { line: 0, column: 0 })
+    }
+    pub fn end(&self) -> LineColumn {
+       self.crumbs.last().map(|c| c.end()).unwrap_or(LineColumn { line: 0, column: 0 })
+    }
+    pub fn trims_left(&self) -> bool {
+       self.crumbs.first().map(|c| c.trims_left()).unwrap_or(false)
+    }
+    pub fn trims_right(&self) -> bool {
+       self.crumbs.last().map(|c| c.trims_right()).unwrap_or(false)
+    }
+}
+
+impl Xhtml {
+    /// Renders the template in the given mode as code that writes to `stream`
+    ///
+    /// In html5 mode, a document that starts with an html element gets a doctype.
+    pub fn render(&self, mode: Mode) -> Emitter {
+        self.render_to(Emitter::new(mode))
+    }
+    /// Renders the template without merging adjacent static text, for comparison
+    pub fn render_unmerged(&self, mode: Mode) -> Emitter {
+        self.render_to(Emitter::unmerged(mode))
+    }
+    fn render_to(&self, mut e: Emitter) -> Emitter {
+        let mode = e.mode();
+        if mode == Mode::Html5 {
+        if let Some(XhtmlCrumb::T(t)) = self.crumbs.iter().find(|c| c.does_emit() && !matches!(c, XhtmlCrumb::N(_))) {
+        if t.tag == "html" {
+           e.push_str("<!DOCTYPE html>", t.outer_span);
+        }}}
+        self.emit(&mut e);
+        e
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+        let mut prev: Option<LineColumn> = None;
+        let mut trim = false;
+        for c in self.crumbs.iter() {
+            //when minified, comments are left out along with their breaking spaces
+            if e.mode() == Mode::Minify && matches!(c, XhtmlCrumb::N(_)) { c.emit(e); continue; }
+            //when minified, there are no breaking spaces next to a block element
+            if e.mode() == Mode::Minify && c.is_block() { trim = true; }
+            if let Some(end) = prev {
+            if c.does_emit() && c.start() > end && !trim && !c.trims_left() {
+               e.push_space(c.span());
+            }}
+
+            prev = Some(c.end());
+            trim = c.trims_right() || (e.mode() == Mode::Minify && c.is_block());
+            c.emit(e);
+        }
+    }
+}
+
+impl Parse for Xhtml {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let crumbs: Vec<XhtmlCrumb> = input.call(XhtmlCrumb::parse_outer)?;
+
+        Ok(Xhtml {
+            crumbs: crumbs
+        })
+    }
+}
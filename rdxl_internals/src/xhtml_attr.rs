@@ -0,0 +1,60 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use syn::parse::{ParseStream, Result};
+use syn::{LitChar, LitBool, LitStr, LitInt, bracketed};
+use syn::token::{Bracket,Brace};
+use crate::xhtml::{XhtmlExpr,BracketedExpr,XhtmlTokenList,XhtmlStyleList};
+
+pub enum XhtmlAttr {
+   S(String),
+   F(BracketedExpr),
+   E(XhtmlExpr),
+   L(XhtmlTokenList),
+   C(XhtmlStyleList)
+}
+impl XhtmlAttr {
+   /// Whether the input starts with [[double brackets]], rather than a [token list]
+   fn peek_double_bracket(input: ParseStream) -> bool {
+      fn double(input: ParseStream) -> Result<bool> {
+         let outer;
+         let inner;
+         bracketed!(outer in input);
+         if !outer.peek(Bracket) { return Ok(false); }
+         bracketed!(inner in outer);
+         let _ = inner;
+         Ok(outer.is_empty())
+      }
+      double(&input.fork()).unwrap_or(false)
+   }
+   pub fn parse(input: ParseStream, key: String) -> Result<Self> {
+      if input.peek(Bracket) && XhtmlStyleList::peek(input) {
+         let c: XhtmlStyleList = input.parse()?;
+         Ok(XhtmlAttr::C(c))
+      } else if input.peek(Bracket) && !XhtmlAttr::peek_double_bracket(input) {
+         let l: XhtmlTokenList = input.parse()?;
+         Ok(XhtmlAttr::L(l))
+      } else if input.peek(Bracket) {
+         let f: BracketedExpr = BracketedExpr::parse(key.clone(),input)?;
+         Ok(XhtmlAttr::F(f))
+      } else if input.peek(Brace) {
+         let e: XhtmlExpr = input.parse()?;
+         Ok(XhtmlAttr::E(e))
+      } else if input.peek(LitBool) {
+         let b: LitBool = input.parse()?;
+         Ok(XhtmlAttr::S(format!("{}", b.value)))
+      } else if input.peek(LitInt) {
+         let b: LitInt = input.parse()?;
+         Ok(XhtmlAttr::S(b.base10_digits().to_string()))
+      } else if input.peek(LitChar) {
+         let b: LitChar = input.parse()?;
+         Ok(XhtmlAttr::S(format!("'{}'", b.value())))
+      } else {
+         let val: LitStr = input.parse()?;
+         Ok(XhtmlAttr::S(format!("{:?}",val.value())))
+      }
+   }
+}
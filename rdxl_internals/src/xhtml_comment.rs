@@ -0,0 +1,151 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, Spacing, LineColumn};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Token};
+use syn::token::{Brace};
+
+use crate::core::{TokenAsLiteral,Emitter,Mode};
+use crate::xhtml::XhtmlExpr;
+use crate::xhtml_expr::XhtmlExprInner;
+
+/// An html comment, like <!-- text {{ expr }} -->
+///
+/// A comment with expressions is escaped as a whole when it is rendered, so that
+/// an interpolated value cannot end it early.
+pub struct XhtmlComment {
+   pub open: Token![<],
+   pub parts: Vec<XhtmlCommentPart>,
+   pub close: Token![>]
+}
+
+pub enum XhtmlCommentPart {
+   S(String, Span),
+   E(XhtmlExpr),
+}
+
+impl XhtmlCommentPart {
+    fn span(&self) -> Span {
+       match self {
+          XhtmlCommentPart::S(_,span) => { span.clone() },
+          XhtmlCommentPart::E(x) => { x.brace_token1.span },
+       }
+    }
+}
+
+/// Whether the next tokens are the puncts in `cs`, written without spaces between them
+fn peek_puncts(input: ParseStream, cs: &[char]) -> bool {
+    let mut cursor = input.cursor();
+    for (i,c) in cs.iter().enumerate() {
+       match cursor.punct() {
+          Some((p,rest)) if p.as_char() == *c && (i+1 == cs.len() || p.spacing() == Spacing::Joint) => { cursor = rest; },
+          _ => { return false; }
+       }
+    }
+    true
+}
+
+impl XhtmlComment {
+    pub fn peek(input: ParseStream) -> bool {
+       peek_puncts(input, &['<','!','-','-'])
+    }
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+       let span = self.span();
+
+       //minified output has no comments, but the expressions still count as used
+       if e.mode() == Mode::Minify {
+          for p in self.parts.iter() {
+          if let XhtmlCommentPart::E(XhtmlExpr { expr: XhtmlExprInner::E(x), .. }) = p {
+             e.push_code(quote_spanned!{syn::spanned::Spanned::span(x)=>
+                if false { let _ = &(#x); }
+             });
+          }}
+          return;
+       }
+
+       //the source spacing is kept, even in xtext mode
+       let mut code = proc_macro2::TokenStream::new();
+       let mut text = String::new();
+       let mut prev: Option<LineColumn> = None;
+       for p in self.parts.iter() {
+          if let Some(end) = prev {
+          if p.span().start() > end {
+             text.push(' ');
+             (quote_spanned!{span=> comment.push(' '); }).to_tokens(&mut code);
+          }}
+          prev = Some(p.span().end());
+          match p {
+             XhtmlCommentPart::S(s,span) => {
+                text.push_str(s);
+                (quote_spanned!{span.clone()=> comment.push_str(#s); }).to_tokens(&mut code);
+             },
+             XhtmlCommentPart::E(x) => {
+                if let XhtmlExprInner::E(x) = &x.expr {
+                   (quote_spanned!{syn::spanned::Spanned::span(x)=>
+                      let _ = ::std::fmt::Write::write_fmt(&mut comment, format_args!("{}", #x));
+                   }).to_tokens(&mut code);
+                }
+             }
+          }
+       }
+
+       if self.parts.iter().all(|p| matches!(p, XhtmlCommentPart::S(_,_))) {
+          e.push_str(&format!("<!-- {} -->", text), span);
+       } else {
+          e.push_str("<!-- ", span);
+          e.push_code(quote_spanned!{span=>
+             {
+                let mut comment = String::new();
+                #code
+                let _ = ::rdxl::escape::comment(&mut stream, &comment);
+             }
+          });
+          e.push_str(" -->", span);
+       }
+    }
+}
+
+impl Parse for XhtmlComment {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _: Token![!] = input.parse()?;
+       let _: Token![-] = input.parse()?;
+       let _: Token![-] = input.parse()?;
+
+       let mut parts = Vec::new();
+       while !peek_puncts(input, &['-','-','>']) {
+          if input.is_empty() {
+             return Err(Error::new(open.span, "unterminated comment, expected -->"));
+          } else if input.peek(Brace) {
+             let x: XhtmlExpr = input.parse()?;
+             if !matches!(x.expr, XhtmlExprInner::E(_)) {
+                return Err(Error::new(x.brace_token1.span, "comments may only contain text and {{ expressions }}"));
+             }
+             parts.push(XhtmlCommentPart::E(x));
+          } else if input.peek(Token![>]) {
+             let gt: Token![>] = input.parse()?;
+             parts.push(XhtmlCommentPart::S(">".to_string(), gt.span));
+          } else {
+             let t: TokenAsLiteral = input.parse()?;
+             parts.push(XhtmlCommentPart::S(t.token_literal, t.span));
+          }
+       }
+
+       let _: Token![-] = input.parse()?;
+       let _: Token![-] = input.parse()?;
+       let close: Token![>] = input.parse()?;
+
+       Ok(XhtmlComment {
+          open: open,
+          parts: parts,
+          close: close,
+       })
+    }
+}
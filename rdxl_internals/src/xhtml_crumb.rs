@@ -0,0 +1,191 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned};
+use proc_macro2::{Span, LineColumn};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token};
+use syn::token::{Bracket,Brace};
+
+use crate::core::{TokenAsLiteral,Emitter};
+use crate::xhtml::{XhtmlTag,XhtmlExpr,BracketedExpr,XhtmlClass,XhtmlFragment,XmlInstruction,XmlCdata,XhtmlComment};
+use crate::xhtml_expr::XhtmlExprInner;
+
+pub enum XhtmlCrumb {
+   S(String, Span),
+   T(XhtmlTag),
+   E(XhtmlExpr),
+   F(BracketedExpr),
+   C(XhtmlClass),
+   G(XhtmlFragment),
+   I(XmlInstruction),
+   D(XmlCdata),
+   N(XhtmlComment)
+}
+
+impl XhtmlCrumb {
+    pub fn does_emit(&self) -> bool {
+       match self {
+          XhtmlCrumb::S(_,_) => { true },
+          XhtmlCrumb::T(_) => { true },
+          XhtmlCrumb::E(e) => { e.does_emit() },
+          XhtmlCrumb::F(_) => { true },
+          XhtmlCrumb::C(_) => { true },
+          XhtmlCrumb::G(g) => { g.inner.crumbs.iter().any(|c| c.does_emit()) },
+          XhtmlCrumb::I(_) => { true },
+          XhtmlCrumb::D(_) => { true },
+          XhtmlCrumb::N(_) => { true },
+       }
+    }
+    /// Whether this crumb is an element that is displayed as a block
+    pub fn is_block(&self) -> bool {
+       match self {
+          XhtmlCrumb::T(t) => { t.is_block() },
+          _ => { false }
+       }
+    }
+    /// Whether the breaking space before this crumb is trimmed
+    pub fn trims_left(&self) -> bool {
+       match self {
+          XhtmlCrumb::E(e) => { e.trims_left() },
+          _ => { false }
+       }
+    }
+    /// Whether the breaking space after this crumb is trimmed
+    pub fn trims_right(&self) -> bool {
+       match self {
+          XhtmlCrumb::E(e) => { e.trims_right() },
+          _ => { false }
+       }
+    }
+    pub fn span(&self) -> Span {
+        match self {
+            XhtmlCrumb::S(_,sp) => { sp.clone() }
+            XhtmlCrumb::T(t) => { t.outer_span.clone() }
+            XhtmlCrumb::E(e) => { e.brace_token1.span.clone() }
+            XhtmlCrumb::F(f) => { f.span() }
+            XhtmlCrumb::C(c) => { c.open.span.join(c.close.span).unwrap_or(c.open.span) }
+            XhtmlCrumb::G(g) => { g.span() }
+            XhtmlCrumb::I(i) => { i.span() }
+            XhtmlCrumb::D(d) => { d.span() }
+            XhtmlCrumb::N(n) => { n.span() }
+        }
+    }
+    pub fn start(&self) -> LineColumn {
+        match self {
+            XhtmlCrumb::T(t) => { t.outer_span_start.start() }
+            XhtmlCrumb::C(c) => { c.open.span.start() }
+            XhtmlCrumb::G(g) => { g.open.span.start() }
+            XhtmlCrumb::I(i) => { i.open.span.start() }
+            XhtmlCrumb::D(d) => { d.open.span.start() }
+            XhtmlCrumb::N(n) => { n.open.span.start() }
+            c => { c.span().start() }
+        }
+    }
+    pub fn end(&self) -> LineColumn {
+        match self {
+            XhtmlCrumb::T(t) => { t.outer_span_end.end() }
+            XhtmlCrumb::E(e) => { e.end() }
+            XhtmlCrumb::C(c) => { c.close.span.end() }
+            XhtmlCrumb::G(g) => { g.close.span.end() }
+            XhtmlCrumb::I(i) => { i.close.span.end() }
+            XhtmlCrumb::D(d) => { d.close.span.end() }
+            XhtmlCrumb::N(n) => { n.close.span.end() }
+            c => { c.span().end() }
+        }
+    }
+    pub fn parse_outer(input: ParseStream) -> Result<Vec<Self>> {
+        let mut cs = vec!();
+        while !input.is_empty() &&
+              !(input.peek(Token![<]) && input.peek2(Token![/])) {
+           let c: XhtmlCrumb = input.parse()?;
+
+           //{{ between }} and {{ else }} statements belong to the for statement before them
+           let c = match (c, cs.last_mut()) {
+              (XhtmlCrumb::E(x), Some(XhtmlCrumb::E(XhtmlExpr { expr: XhtmlExprInner::F(f), .. }))) => {
+                 match f.attach(x) {
+                    None => { continue; }
+                    Some(x) => { XhtmlCrumb::E(x) }
+                 }
+              },
+              (c, _) => { c }
+           };
+           cs.push(c);
+        }
+        Ok(cs)
+    }
+}
+
+impl Parse for XhtmlCrumb {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if XhtmlFragment::peek(input) {
+           let g: XhtmlFragment = input.parse()?;
+           Ok(XhtmlCrumb::G(g))
+        } else if XmlInstruction::peek(input) {
+           let i: XmlInstruction = input.parse()?;
+           Ok(XhtmlCrumb::I(i))
+        } else if XhtmlComment::peek(input) {
+           let n: XhtmlComment = input.parse()?;
+           Ok(XhtmlCrumb::N(n))
+        } else if XmlCdata::peek(input) {
+           let d: XmlCdata = input.parse()?;
+           Ok(XhtmlCrumb::D(d))
+        } else if input.peek(Token![<]) && input.peek2(Token![!]) {
+           let c: XhtmlClass = input.parse()?;
+           Ok(XhtmlCrumb::C(c))
+        } else if input.peek(Token![<]) {
+           let t: XhtmlTag = input.parse()?;
+           Ok(XhtmlCrumb::T(t))
+        } else if input.peek(Bracket) {
+           let f: BracketedExpr = BracketedExpr::parse("markup".to_string(),input)?;
+           Ok(XhtmlCrumb::F(f))
+        } else if input.peek(Brace) {
+           let e: XhtmlExpr = input.parse()?;
+           Ok(XhtmlCrumb::E(e))
+        } else {
+           let t: TokenAsLiteral = input.parse()?;
+           Ok(XhtmlCrumb::S(t.token_literal, t.span))
+        }
+    }
+}
+
+impl XhtmlCrumb {
+    pub fn emit(&self, e: &mut Emitter) {
+        match self {
+           XhtmlCrumb::S(s,span) => {
+              e.push_text(s, span.clone());
+           },
+           XhtmlCrumb::T(t) => {
+              t.emit(e);
+           }
+           XhtmlCrumb::E(x) => {
+              x.emit(e);
+           }
+           XhtmlCrumb::F(f) => {
+              f.emit(e);
+           }
+           XhtmlCrumb::G(g) => {
+              g.emit(e);
+           }
+           XhtmlCrumb::I(i) => {
+              i.emit(e);
+           }
+           XhtmlCrumb::D(d) => {
+              d.emit(e);
+           }
+           XhtmlCrumb::N(n) => {
+              n.emit(e);
+           }
+           XhtmlCrumb::C(c) => {
+              let span = c.span();
+              let c = c.expr(e);
+              e.push_code(quote_spanned!{span=>
+                 let _ = ::std::fmt::Write::write_fmt(&mut stream, format_args!("{}", #c));
+              });
+           }
+        }
+    }
+}
@@ -0,0 +1,555 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, LineColumn, TokenTree};
+use syn::parse::{Parse, ParseStream, Parser, Result, Error};
+use syn::{Token, Expr, Pat, PatOr, Label, Lifetime, Ident, braced};
+use syn::punctuated::Punctuated;
+use syn::token::{Brace};
+use crate::xhtml::XhtmlCrumb;
+use crate::core::{Emitter,Mode};
+
+pub enum XhtmlExprInner {
+   S(Expr),
+   E(Expr),
+   F(XhtmlFor),
+   W(Option<Label>,Token![while],Expr,Vec<XhtmlCrumb>),
+   L(Token![let],Pat,Expr),
+   I(Token![if],Expr,Vec<XhtmlCrumb>,Vec<(Expr,Vec<XhtmlCrumb>)>,Vec<XhtmlCrumb>),
+   P(Option<Label>,Token![loop],Vec<XhtmlCrumb>),
+   M(Token![match],Expr,Vec<XhtmlMatchArm>),
+   B(Ident,Vec<XhtmlCrumb>),
+   X(Token![else],Vec<XhtmlCrumb>),
+   K(Ident,Vec<XhtmlCrumb>),
+   A(Token![+]),
+   N(Token![#]),
+}
+
+/// An arm of a match statement, whose body is markup
+pub struct XhtmlMatchArm {
+   pub pat: Pat,
+   pub guard: Option<Expr>,
+   pub body: Vec<XhtmlCrumb>
+}
+impl Parse for XhtmlMatchArm {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let leading_vert: Option<Token![|]> = input.parse()?;
+       let first: Pat = input.parse()?;
+       let pat = if leading_vert.is_some() || (input.peek(Token![|]) && !input.peek(Token![||])) {
+          let mut cases = Punctuated::new();
+          cases.push_value(first);
+          while input.peek(Token![|]) && !input.peek(Token![||]) {
+             cases.push_punct(input.parse()?);
+             cases.push_value(input.parse()?);
+          }
+          Pat::Or(PatOr { attrs: Vec::new(), leading_vert: leading_vert, cases: cases })
+       } else { first };
+       let guard = if input.peek(Token![if]) {
+          let _if: Token![if] = input.parse()?;
+          Some(input.parse()?)
+       } else { None };
+       let _arrow: Token![=>] = input.parse()?;
+       let content;
+       let content2;
+       let _brace1 = braced!(content in input);
+       let _brace2 = braced!(content2 in content);
+       let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+       Ok(XhtmlMatchArm { pat: pat, guard: guard, body: body })
+    }
+}
+impl XhtmlExprInner {
+    pub fn does_emit(&self) -> bool {
+       match self {
+          XhtmlExprInner::S(_) => { false },
+          XhtmlExprInner::E(_) => { true },
+          XhtmlExprInner::F(_) => { true },
+          XhtmlExprInner::B(_,_) => { false },
+          XhtmlExprInner::X(_,_) => { false },
+          XhtmlExprInner::K(_,_) => { true },
+          XhtmlExprInner::A(_) => { true },
+          XhtmlExprInner::N(_) => { false },
+          XhtmlExprInner::P(_,_,_) => { true },
+          XhtmlExprInner::W(_,_,_,_) => { true },
+          XhtmlExprInner::L(_,_,_) => { false },
+          XhtmlExprInner::I(_,_,_,_,_) => { true },
+          XhtmlExprInner::M(_,_,_) => { true },
+       }
+    }
+}
+/// A for statement, which may bind its Loop position and have between and else blocks
+///
+/// The between and else blocks are written as the statements after the for statement.
+pub struct XhtmlFor {
+   pub label: Option<Label>,
+   pub for_token: Token![for],
+   pub pat: Pat,
+   pub iter: Expr,
+   pub meta: Option<Ident>,
+   pub body: Vec<XhtmlCrumb>,
+   pub between: Option<Vec<XhtmlCrumb>>,
+   pub empty: Option<Vec<XhtmlCrumb>>,
+   /// The outer braces of the last between or else statement
+   pub last: Option<Brace>,
+}
+impl XhtmlFor {
+    /// Attaches a following {{ between }} or {{ else }} statement, or returns it if it doesn't belong to this loop
+    pub fn attach(&mut self, x: XhtmlExpr) -> Option<XhtmlExpr> {
+       match x.expr {
+          XhtmlExprInner::B(_,cs) if self.between.is_none() && self.empty.is_none() => {
+             self.between = Some(cs); self.last = Some(x.brace_token1); None
+          }
+          XhtmlExprInner::X(_,cs) if self.empty.is_none() => {
+             self.empty = Some(cs); self.last = Some(x.brace_token1); None
+          }
+          _ => { Some(x) }
+       }
+    }
+    pub fn emit(&self, e: &mut Emitter, trim_right: bool) {
+        let span = self.for_token.span;
+        let (a, p, i) = (&self.label, &self.pat, &self.iter);
+        let body = XhtmlExprInner::emit_body(&self.body, span, trim_right, e);
+
+        //the else block renders when the loop body never ran
+        let empty = Ident::new("empty", Span::mixed_site());
+        let ran = self.empty.as_ref().map(|_| quote_spanned!{span=> #empty = false; });
+
+        let code = if self.meta.is_none() && self.between.is_none() {
+           quote_spanned!{span=>
+              #a for #p in #i { #ran #body }
+           }
+        } else {
+           let meta = self.meta.clone().unwrap_or_else(|| Ident::new("meta", Span::mixed_site()));
+           let between = self.between.as_ref().map(|cs| {
+              let b = XhtmlExprInner::emit_body(cs, span, trim_right, e);
+              quote_spanned!{span=> if !#meta.is_first() { #b } }
+           });
+           quote_spanned!{span=>
+              #a for (#meta, #p) in ::rdxl::Loop::over(#i) { #ran #between #body }
+           }
+        };
+
+        match &self.empty {
+           None => { e.push_code(code); }
+           Some(cs) => {
+              let el = XhtmlExprInner::emit_body(cs, span, trim_right, e);
+              e.push_code(quote_spanned!{span=>
+                 {
+                    let mut #empty = true;
+                    #code
+                    if #empty { #el }
+                 }
+              });
+           }
+        }
+    }
+}
+
+impl XhtmlExprInner {
+    /// Whether a loop body contains a {{ break value }} statement for the loop with this label,
+    /// looking into nested loops only for labeled breaks
+    fn breaks_with_value(cs: &[XhtmlCrumb], label: Option<&Lifetime>, nested: bool) -> bool {
+        cs.iter().any(|c| match c {
+           XhtmlCrumb::T(t) => { XhtmlExprInner::breaks_with_value(&t.inner.crumbs, label, nested) },
+           XhtmlCrumb::E(x) => { match &x.expr {
+              XhtmlExprInner::S(Expr::Break(b)) | XhtmlExprInner::E(Expr::Break(b)) => {
+                 b.expr.is_some() && match (&b.label, label) {
+                    (None, _) => { !nested },
+                    (Some(b), Some(l)) => { b.ident == l.ident },
+                    (Some(_), None) => { false },
+                 }
+              },
+              XhtmlExprInner::I(_,_,bs,es,el) => {
+                 XhtmlExprInner::breaks_with_value(bs, label, nested) ||
+                 es.iter().any(|(_,cs)| XhtmlExprInner::breaks_with_value(cs, label, nested)) ||
+                 XhtmlExprInner::breaks_with_value(el, label, nested)
+              },
+              XhtmlExprInner::M(_,_,arms) => {
+                 arms.iter().any(|a| XhtmlExprInner::breaks_with_value(&a.body, label, nested))
+              },
+              XhtmlExprInner::F(f) => {
+                 XhtmlExprInner::breaks_with_value(&f.body, label, true) ||
+                 f.between.as_ref().map(|cs| XhtmlExprInner::breaks_with_value(cs, label, true)).unwrap_or(false) ||
+                 f.empty.as_ref().map(|cs| XhtmlExprInner::breaks_with_value(cs, label, nested)).unwrap_or(false)
+              },
+              XhtmlExprInner::W(_,_,_,cs) | XhtmlExprInner::P(_,_,cs) => {
+                 XhtmlExprInner::breaks_with_value(cs, label, true)
+              },
+              _ => { false }
+           }},
+           _ => { false }
+        })
+    }
+    /// Emits the body of a statement, with a trailing breaking space unless the statement trims its right side
+    fn emit_body(cs: &[XhtmlCrumb], span: Span, trim_right: bool, e: &mut Emitter) -> proc_macro2::TokenStream {
+        //a body that ends with break, continue or return has no trailing space, which would be unreachable
+        let diverges = match cs.last() {
+           Some(XhtmlCrumb::E(x)) => { matches!(&x.expr, XhtmlExprInner::S(Expr::Break(_)) |
+              XhtmlExprInner::S(Expr::Continue(_)) | XhtmlExprInner::S(Expr::Return(_))) },
+           _ => { false }
+        };
+        e.nested(|e| {
+           for c in cs.iter() { c.emit(e); }
+           let block = e.mode() == Mode::Minify && cs.last().map(|c| c.is_block()).unwrap_or(false);
+           if !diverges && !trim_right && !block { e.push_space(span); }
+        })
+    }
+    pub fn emit(&self, e: &mut Emitter, trim_right: bool) {
+        match self {
+           XhtmlExprInner::E(x) => {
+              let write = e.write_text(syn::spanned::Spanned::span(x));
+              e.push_code(quote_spanned!{ syn::spanned::Spanned::span(x)=>
+                 {
+                    #[allow(unused_imports)]
+                    use ::rdxl::interpolate::{RawInterpolate as _, EscapedInterpolate as _};
+                    (&::rdxl::interpolate::Interpolate(&(#x))).#write(&mut stream);
+                 }
+              });
+           }, XhtmlExprInner::S(x) => {
+              e.push_code(quote_spanned!{ syn::spanned::Spanned::span(x)=>
+                 #x;
+              });
+           }, XhtmlExprInner::F(f) => {
+              f.emit(e, trim_right);
+           }, XhtmlExprInner::B(b,_) => {
+              e.push_code(Error::new(b.span(), "between must follow a for statement").to_compile_error());
+           }, XhtmlExprInner::X(x,_) => {
+              e.push_code(Error::new(x.span, "else must follow a for statement").to_compile_error());
+           }, XhtmlExprInner::A(a) => {
+              e.push_str(" ", a.span);
+           }, XhtmlExprInner::N(_) => {
+           }, XhtmlExprInner::K(b,cs) => {
+              let body = e.nested(|e| { for c in cs.iter() { c.emit(e); } });
+              match e.layout_trait().cloned() {
+                 None => {
+                    e.push_code(Error::new(b.span(), "block must be in xlayout! or in an xrender! that extends a layout").to_compile_error());
+                 },
+                 Some(_) if b == "layout" => {
+                    e.push_code(Error::new(b.span(), "layout is not a valid block name").to_compile_error());
+                 },
+                 Some(l) => {
+                    if !e.define_block(b.clone(), body) {
+                       e.push_code(Error::new(b.span(), format!("block {} is already defined", b)).to_compile_error());
+                    }
+                    e.push_code(quote_spanned!{b.span()=>
+                       <Self as #l>::#b(self, stream);
+                    });
+                 }
+              }
+           }, XhtmlExprInner::P(a,l,cs) => {
+              let body = XhtmlExprInner::emit_body(cs, l.span, trim_right, e);
+              if XhtmlExprInner::breaks_with_value(cs, a.as_ref().map(|a| &a.name), false) {
+                 //the value of the loop is rendered after it
+                 let value = Ident::new("value", Span::mixed_site());
+                 let write = e.write_text(l.span);
+                 e.push_code(quote_spanned!{l.span=>
+                    {
+                       let #value = #a loop { #body };
+                       #[allow(unused_imports)]
+                       use ::rdxl::interpolate::{RawInterpolate as _, EscapedInterpolate as _};
+                       (&::rdxl::interpolate::Interpolate(&#value)).#write(&mut stream);
+                    }
+                 });
+              } else {
+                 e.push_code(quote_spanned!{l.span=>
+                    #a loop { #body }
+                 });
+              }
+           }, XhtmlExprInner::I(i,c,bs,es,el) => {
+              let body = XhtmlExprInner::emit_body(bs, i.span, trim_right, e);
+              let mut code = quote_spanned!{i.span=>
+                if #c { #body }
+              };
+
+              for (c,cs) in es.iter() {
+                 let body = XhtmlExprInner::emit_body(cs, i.span, trim_right, e);
+                 (quote_spanned!{i.span=>
+                    else if #c { #body }
+                 }).to_tokens(&mut code);
+              }
+
+              if el.len() > 0 {
+                 let body = XhtmlExprInner::emit_body(el, i.span, trim_right, e);
+                 (quote_spanned!{i.span=>
+                    else { #body }
+                 }).to_tokens(&mut code);
+              }
+              e.push_code(code);
+           }, XhtmlExprInner::M(m,x,arms) => {
+              let mut code = proc_macro2::TokenStream::new();
+              for arm in arms.iter() {
+                 let pat = &arm.pat;
+                 let guard = arm.guard.as_ref().map(|g| quote_spanned!{m.span=> if #g });
+                 let body = XhtmlExprInner::emit_body(&arm.body, m.span, trim_right, e);
+                 (quote_spanned!{m.span=>
+                    #pat #guard => { #body }
+                 }).to_tokens(&mut code);
+              }
+              e.push_code(quote_spanned!{m.span=>
+                 match #x { #code }
+              });
+           }, XhtmlExprInner::W(a,w,i,cs) => {
+              let body = XhtmlExprInner::emit_body(cs, w.span, trim_right, e);
+              e.push_code(quote_spanned!{w.span=>
+                 #a while #i { #body }
+              });
+           }, XhtmlExprInner::L(t,l,x) => {
+              e.push_code(quote_spanned!{t.span=>
+                 let #l = #x;
+              });
+           }
+        }
+    }
+}
+impl Parse for XhtmlExprInner {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let label: Option<Label> = if input.peek(Lifetime) && input.peek2(Token![:]) {
+          let label: Label = input.parse()?;
+          if !(input.peek(Token![for]) || input.peek(Token![while]) || input.peek(Token![loop])) {
+             let msg = "Expected for, while or loop after a label";
+             return Err(Error::new(label.name.span(), msg));
+          }
+          Some(label)
+       } else { None };
+
+       if input.peek(Token![for]) {
+          let _for: Token![for] = input.parse()?;
+          let pat: Pat = input.parse()?;
+          let _in: Token![in] = input.parse()?;
+          let iter: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let meta = if input.peek(Ident) && input.peek2(Ident) {
+             let with: Ident = input.parse()?;
+             if with != "with" {
+                return Err(Error::new(with.span(), "Expected with or a block after the for iterator"));
+             }
+             Some(input.parse()?)
+          } else { None };
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::F(XhtmlFor {
+             label: label,
+             for_token: _for,
+             pat: pat,
+             iter: iter,
+             meta: meta,
+             body: body,
+             between: None,
+             empty: None,
+             last: None,
+          }))
+       } else if input.peek(Token![loop]) {
+          let _loop: Token![loop] = input.parse()?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::P(label,_loop,body))
+       } else if input.peek(Ident) && input.peek2(Brace) && input.fork().parse::<Ident>()? == "between" {
+          let between: Ident = input.parse()?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::B(between,body))
+       } else if input.peek(Ident) && input.peek2(Ident) && input.peek3(Brace) && input.fork().parse::<Ident>()? == "block" {
+          let _block: Ident = input.parse()?;
+          let name: Ident = input.parse()?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::K(name,body))
+       } else if input.peek(Token![else]) {
+          let _else: Token![else] = input.parse()?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::X(_else,body))
+       } else if input.peek(Token![match]) {
+          let _match: Token![match] = input.parse()?;
+          let x: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          //as in Rust, the comma after a braced arm is optional
+          let mut arms = Vec::new();
+          while !content2.is_empty() {
+             arms.push(content2.parse()?);
+             if content2.peek(Token![,]) {
+                let _comma: Token![,] = content2.parse()?;
+             }
+          }
+          Ok(XhtmlExprInner::M(_match,x,arms))
+       } else if input.peek(Token![while]) {
+          let _while: Token![while] = input.parse()?;
+          let iter: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+          Ok(XhtmlExprInner::W(label,_while,iter,body))
+       } else if input.peek(Token![if]) {
+          let _if: Token![if] = input.parse()?;
+          let b: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let mut es = Vec::new();
+          let mut e = Vec::new();
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<XhtmlCrumb> = content2.call(XhtmlCrumb::parse_outer)?;
+
+          while input.peek(Token![else]) && input.peek2(Token![if]) {
+             let _else: Token![else] = input.parse()?;
+             let _if: Token![if] = input.parse()?;
+             let b: Expr = input.call(Expr::parse_without_eager_brace)?;
+             let content;
+             let content2;
+             let _brace1 = braced!(content in input);
+             let _brace2 = braced!(content2 in content);
+             let e = content2.call(XhtmlCrumb::parse_outer)?;
+             es.push((b,e));
+          }
+
+          if input.peek(Token![else]) {
+             let _else: Token![else] = input.parse()?;
+             let content;
+             let content2;
+             let _brace1 = braced!(content in input);
+             let _brace2 = braced!(content2 in content);
+             e = content2.call(XhtmlCrumb::parse_outer)?;
+          }
+
+          Ok(XhtmlExprInner::I(_if,b,body,es,e))
+       } else if input.peek(Token![let]) {
+          let _let: Token![let] = input.parse()?;
+          let pat: Pat = input.parse()?;
+          let _eq: Token![=] = input.parse()?;
+          let expr: Expr = input.parse()?;
+          if input.peek(Token![;]) {
+             let _: Token![;] = input.parse()?;
+          }
+          Ok(XhtmlExprInner::L(_let,pat,expr))
+       } else {
+          let e: Expr = input.parse()?;
+          if input.peek(Token![;]) {
+             let _semi: Token![;] = input.parse()?;
+             Ok(XhtmlExprInner::S(e))
+          } else if let Expr::Break(_) | Expr::Continue(_) | Expr::Return(_) = e {
+             //control flow has no value to render
+             Ok(XhtmlExprInner::S(e))
+          } else {
+             Ok(XhtmlExprInner::E(e))
+          }
+       }
+    }
+}
+
+/// A statement or expression in {{double braces}}
+///
+/// A `-` just inside of the braces trims the breaking space on that side, and {{+}} is a space
+/// that is always rendered, in place of any breaking spaces around it.
+pub struct XhtmlExpr {
+   pub brace_token1: Brace,
+   pub brace_token2: Brace,
+   pub trim_left: Option<Token![-]>,
+   pub trim_right: Option<Token![-]>,
+   pub expr: XhtmlExprInner
+}
+impl XhtmlExpr {
+    pub fn does_emit(&self) -> bool {
+       self.expr.does_emit()
+    }
+    pub fn trims_left(&self) -> bool {
+       self.trim_left.is_some() || matches!(self.expr, XhtmlExprInner::A(_))
+    }
+    pub fn trims_right(&self) -> bool {
+       self.trim_right.is_some() || matches!(self.expr, XhtmlExprInner::A(_))
+    }
+    /// The end of the statement, including the between and else statements that belong to a for statement
+    pub fn end(&self) -> LineColumn {
+       match &self.expr {
+          XhtmlExprInner::F(XhtmlFor { last: Some(b), .. }) => { b.span.end() },
+          _ => { self.brace_token1.span.end() }
+       }
+    }
+    /// The name and body of a {{ block }} statement
+    pub fn block(&self) -> Option<(&Ident, &[XhtmlCrumb])> {
+       match &self.expr {
+          XhtmlExprInner::K(b,cs) => { Some((b, cs)) },
+          _ => { None }
+       }
+    }
+}
+impl Parse for XhtmlExpr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let _content;
+        let content2;
+        let brace_token1 = braced!(_content in input);
+        let brace_token2 = braced!(content2 in _content);
+
+        //a template comment {{# ... }} is dropped along with everything in it
+        if content2.peek(Token![#]) && !content2.peek2(syn::token::Bracket) {
+           let hash: Token![#] = content2.parse()?;
+           let _rest: proc_macro2::TokenStream = content2.parse()?;
+           return Ok(XhtmlExpr {
+              brace_token1: brace_token1,
+              brace_token2: brace_token2,
+              trim_left: None,
+              trim_right: None,
+              expr: XhtmlExprInner::N(hash),
+           });
+        }
+
+        let fork = content2.fork();
+        if fork.peek(Token![+]) && { let _plus: Token![+] = fork.parse()?; fork.is_empty() } {
+           return Ok(XhtmlExpr {
+              brace_token1: brace_token1,
+              brace_token2: brace_token2,
+              trim_left: None,
+              trim_right: None,
+              expr: XhtmlExprInner::A(content2.parse()?),
+           });
+        }
+
+        let trim_left: Option<Token![-]> = content2.parse()?;
+
+        //no statement or expression ends with a -, so a trailing one is a trim marker
+        let mut tokens: Vec<TokenTree> = content2.fork().parse::<proc_macro2::TokenStream>()?.into_iter().collect();
+        let (trim_right, expr) = match tokens.last() {
+           Some(TokenTree::Punct(p)) if p.as_char() == '-' => {
+              let trim = Token![-](p.span());
+              tokens.pop();
+              let _rest: proc_macro2::TokenStream = content2.parse()?;
+              (Some(trim), XhtmlExprInner::parse.parse2(tokens.into_iter().collect())?)
+           },
+           _ => { (None, content2.call(XhtmlExprInner::parse)?) }
+        };
+
+        Ok(XhtmlExpr {
+           brace_token1: brace_token1,
+           brace_token2: brace_token2,
+           trim_left: trim_left,
+           trim_right: trim_right,
+           expr: expr,
+        })
+    }
+}
+impl XhtmlExpr {
+    pub fn emit(&self, e: &mut Emitter) {
+        self.expr.emit(e, self.trim_right.is_some())
+    }
+}
+
@@ -0,0 +1,61 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token};
+
+use crate::xhtml::Xhtml;
+use crate::core::Emitter;
+
+/// A fragment <>...</>, which renders its children without a wrapping tag
+pub struct XhtmlFragment {
+   pub open: Token![<],
+   pub inner: Xhtml,
+   pub close: Token![>]
+}
+
+impl XhtmlFragment {
+    pub fn peek(input: ParseStream) -> bool {
+       input.peek(Token![<]) && input.peek2(Token![>])
+    }
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+       self.inner.emit(e);
+    }
+    /// The fragment rendered as Markup, for use as an attribute value or child of a custom type
+    pub fn expr(&self, e: &mut Emitter) -> proc_macro2::TokenStream {
+       let body = e.nested(|e| self.emit(e));
+       quote_spanned! {self.span()=>
+          {
+             let mut stream = String::new();
+             #body
+             ::rdxl::PreEscaped(stream)
+          }
+       }
+    }
+}
+
+impl Parse for XhtmlFragment {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _: Token![>] = input.parse()?;
+
+       let inner: Xhtml = input.parse()?;
+
+       let _: Token![<] = input.parse()?;
+       let _: Token![/] = input.parse()?;
+       let close: Token![>] = input.parse()?;
+
+       Ok(XhtmlFragment {
+          open: open,
+          inner: inner,
+          close: close,
+       })
+    }
+}
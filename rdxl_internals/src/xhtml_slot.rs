@@ -0,0 +1,67 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Ident, Token};
+
+use crate::xhtml::Xhtml;
+use crate::core::Emitter;
+
+/// The content of a named slot, like <:header>markup</:header>
+pub struct XhtmlSlot {
+   pub open: Token![<],
+   pub name: Ident,
+   pub inner: Xhtml,
+   pub close: Token![>]
+}
+
+impl XhtmlSlot {
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    /// The slot content, rendered as Markup in the same mode as the rest of the template
+    pub fn expr(&self, e: &Emitter) -> proc_macro2::TokenStream {
+       let span = self.span();
+       let inner = self.inner.render(e.mode());
+       let capacity = inner.capacity();
+       quote_spanned!{span=>
+          {
+             let mut stream = String::with_capacity(#capacity);
+             #inner
+             ::rdxl::PreEscaped(stream)
+          }
+       }
+    }
+}
+
+impl Parse for XhtmlSlot {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _colon: Token![:] = input.parse()?;
+       let name: Ident = input.parse()?;
+       let _gt: Token![>] = input.parse()?;
+
+       let inner: Xhtml = input.parse()?;
+
+       let _lt: Token![<] = input.parse()?;
+       let _slash: Token![/] = input.parse()?;
+       let _colon: Token![:] = input.parse()?;
+       let close_name: Ident = input.parse()?;
+       if name != close_name {
+          let msg = format!("Expected </:{}> found </:{}>", name, close_name);
+          return Err(Error::new(close_name.span(), msg));
+       }
+       let close: Token![>] = input.parse()?;
+
+       Ok(XhtmlSlot {
+          open: open,
+          name: name,
+          inner: inner,
+          close: close
+       })
+    }
+}
@@ -0,0 +1,298 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::{Literal, TokenStream, TokenTree, Delimiter};
+
+/// A template written as real HTML, where only {{ }} islands are Rust
+///
+/// Lexing translates the template into the source of an equivalent <b>xtext!</b> template:
+/// each run of HTML becomes a string literal, which is rendered verbatim, and each island
+/// is copied as it was written. The bodies of statements inside an island are HTML again.
+pub struct XhtmlStr {
+   pub source: String,
+   //(offset in source, offset in template, whether the text was copied as written)
+   map: Vec<(usize, usize, bool)>,
+}
+
+/// An error in a template, at a byte offset of the template
+pub struct XhtmlStrError {
+   pub offset: usize,
+   pub message: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Html {
+   Text,
+   Tag,
+   Quoted(char),
+}
+
+struct Lexer<'a> {
+   html: &'a str,
+   pos: usize,
+   out: XhtmlStr,
+   html_state: Html,
+}
+
+impl<'a> Lexer<'a> {
+   fn rest(&self) -> &'a str {
+      &self.html[self.pos..]
+   }
+   fn copy(&mut self, from: usize, to: usize) {
+      if from == to { return; }
+      self.out.map.push((self.out.source.len(), from, true));
+      self.out.source.push_str(&self.html[from..to]);
+   }
+   fn text(&mut self, from: usize, to: usize) {
+      if from == to { return; }
+      self.out.map.push((self.out.source.len(), from, false));
+      self.out.source.push_str(&Literal::string(&self.html[from..to]).to_string());
+   }
+   fn insert(&mut self, s: &str, at: usize) {
+      self.out.map.push((self.out.source.len(), at, false));
+      self.out.source.push_str(s);
+   }
+   fn unclosed(open: usize) -> XhtmlStrError {
+      XhtmlStrError { offset: open, message: "Expected }} to close this {{".to_string() }
+   }
+   fn unbalanced(open: usize) -> XhtmlStrError {
+      XhtmlStrError { offset: open, message: "This bracket is not closed".to_string() }
+   }
+   /// HTML text up to the end of the template, or to the }} that closes a body
+   fn markup(&mut self, open: Option<usize>) -> Result<(), XhtmlStrError> {
+      let mut start = self.pos;
+      loop {
+         let rest = self.rest();
+         if rest.is_empty() {
+            self.text(start, self.pos);
+            return match open { None => Ok(()), Some(o) => Err(Lexer::unclosed(o)) };
+         } else if rest.starts_with("{{") {
+            self.text(start, self.pos);
+            self.island()?;
+            start = self.pos;
+         } else if open.is_some() && rest.starts_with("}}") {
+            self.text(start, self.pos);
+            return Ok(());
+         } else {
+            let mut chars = rest.chars();
+            let c = chars.next().unwrap_or(' ');
+            self.html_state = match (self.html_state, c) {
+               (Html::Text, '<') if chars.next().map(|n| n.is_alphabetic() || n == '/').unwrap_or(false) => { Html::Tag },
+               (Html::Tag, '>') => { Html::Text },
+               (Html::Tag, '"') | (Html::Tag, '\'') => { Html::Quoted(c) },
+               (Html::Quoted(q), c) if c == q => { Html::Tag },
+               (state, _) => { state },
+            };
+            self.pos += c.len_utf8();
+         }
+      }
+   }
+   /// An island, starting at its {{
+   ///
+   /// An expression inside of a tag is escaped as an attribute value, because text escaping
+   /// would not keep it from closing the quotes around it.
+   fn island(&mut self) -> Result<(), XhtmlStrError> {
+      let open = self.pos;
+      self.pos += 2;
+      let quoted = self.html_state != Html::Text && self.is_expression(open)?;
+      self.copy(open, self.pos);
+      if quoted { self.insert("::rdxl::interpolate::Quoted(&(", self.pos); }
+      self.code(open)?;
+      if quoted { self.insert("))", self.pos); }
+      self.copy(self.pos, self.pos + 2);
+      self.pos += 2;
+      Ok(())
+   }
+   /// Whether the island at the current position is an expression rather than a statement
+   fn is_expression(&self, open: usize) -> Result<bool, XhtmlStrError> {
+      let mut end = Lexer { html: self.html, pos: self.pos, out: XhtmlStr { source: String::new(), map: Vec::new() }, html_state: self.html_state };
+      end.code(open)?;
+      let code = self.html[self.pos..end.pos].trim();
+      let word: String = code.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+      let statement = ["for", "while", "loop", "if", "match", "let", "block", "between", "else", "break", "continue", "return"];
+      Ok(!(statement.contains(&word.as_str()) || code.starts_with('\'') || code.ends_with(';')))
+   }
+   /// Rust code up to the }} that closes the island, where {{ opens a body of markup
+   ///
+   /// The first {{ of a match statement holds its arms, which are Rust code again.
+   fn code(&mut self, open: usize) -> Result<(), XhtmlStrError> {
+      let trimmed = self.rest().trim_start();
+      let mut arms = trimmed.starts_with("match") &&
+                     trimmed[5..].starts_with(|c: char| c.is_whitespace() || c == '(');
+      //the brackets that are open in the code, which the compiler would not lex unless they are balanced
+      let mut brackets: Vec<(char, usize)> = Vec::new();
+      let mut start = self.pos;
+      loop {
+         let rest = self.rest();
+         let mut chars = rest.chars();
+         let c = match chars.next() {
+            None => { return Err(brackets.first().map(|&(_,o)| Lexer::unbalanced(o)).unwrap_or(Lexer::unclosed(open))); },
+            Some(c) => { c },
+         };
+         if brackets.is_empty() && rest.starts_with("}}") {
+            self.copy(start, self.pos);
+            return Ok(());
+         } else if rest.starts_with("{{") {
+            let body = self.pos;
+            self.copy(start, body + 2);
+            self.pos += 2;
+            if arms {
+               arms = false;
+               self.code(body)?;
+            } else {
+               self.markup(Some(body))?;
+            }
+            self.copy(self.pos, self.pos + 2);
+            self.pos += 2;
+            start = self.pos;
+         } else if c == '{' || c == '(' || c == '[' {
+            brackets.push((c, self.pos));
+            self.pos += 1;
+         } else if c == '}' || c == ')' || c == ']' {
+            match brackets.pop() {
+               Some(('{', _)) if c == '}' => {},
+               Some(('(', _)) if c == ')' => {},
+               Some(('[', _)) if c == ']' => {},
+               Some((_, o)) => { return Err(Lexer::unbalanced(o)); },
+               None => { return Err(XhtmlStrError { offset: self.pos, message: format!("Unexpected {} here", c) }); },
+            }
+            self.pos += 1;
+         } else if c == '"' {
+            self.pos += Lexer::quoted(rest, '"');
+         } else if c == '\'' {
+            //a char literal, or else the quote of a lifetime or label
+            self.pos += match (chars.next(), chars.next()) {
+               (Some('\\'), _) => { Lexer::quoted(rest, '\'') },
+               (Some(c), Some('\'')) => { 2 + c.len_utf8() },
+               _ => { 1 },
+            };
+         } else if c == 'r' && Lexer::raw_string(rest).is_some() && !self.html[..self.pos].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+            self.pos += Lexer::raw_string(rest).unwrap_or(1);
+         } else if rest.starts_with("//") {
+            self.pos += rest.find('\n').unwrap_or(rest.len());
+         } else if rest.starts_with("/*") {
+            self.pos += rest.find("*/").map(|e| e + 2).unwrap_or(rest.len());
+         } else {
+            self.pos += c.len_utf8();
+         }
+      }
+   }
+   /// The length of a quoted literal with backslash escapes
+   fn quoted(s: &str, quote: char) -> usize {
+      let mut escaped = false;
+      for (i,c) in s.char_indices().skip(1) {
+         if escaped { escaped = false; }
+         else if c == '\\' { escaped = true; }
+         else if c == quote { return i + 1; }
+      }
+      s.len()
+   }
+   /// The length of a raw string literal, if the text starts with one
+   fn raw_string(s: &str) -> Option<usize> {
+      let hashes = s[1..].chars().take_while(|c| *c == '#').count();
+      if !s[1 + hashes..].starts_with('"') { return None; }
+      let close = format!("\"{}", "#".repeat(hashes));
+      let body = 2 + hashes;
+      Some(s[body..].find(&close).map(|e| body + e + close.len()).unwrap_or(s.len()))
+   }
+}
+
+impl XhtmlStr {
+   pub fn lex(html: &str) -> Result<XhtmlStr, XhtmlStrError> {
+      let mut lexer = Lexer {
+         html: html,
+         pos: 0,
+         out: XhtmlStr { source: String::new(), map: Vec::new() },
+         html_state: Html::Text,
+      };
+      lexer.markup(None)?;
+      //the end of the template, which ends the range of the last piece
+      lexer.out.map.push((lexer.out.source.len(), html.len(), true));
+      Ok(lexer.out)
+   }
+   /// The template ranges of the tokens lexed from the translated source, in the order that a token
+   /// and then the tokens inside of it are visited
+   ///
+   /// Inserted tokens have an empty range and a literal of HTML text has the range of that text.
+   /// Nothing is located when a doc comment, which is lexed as an attribute, does not match the source.
+   pub fn locate(&self, ts: TokenStream) -> Option<Vec<(usize,usize)>> {
+      let mut at = 0;
+      let mut out = Vec::new();
+      self.locate_in(ts, &mut at, &mut out)?;
+      Some(out)
+   }
+   fn locate_in(&self, ts: TokenStream, at: &mut usize, out: &mut Vec<(usize,usize)>) -> Option<()> {
+      for t in ts {
+         *at = self.skip_comments(*at)?;
+         let start = *at;
+         let index = out.len();
+         out.push((0, 0));
+         match t {
+            TokenTree::Group(g) => {
+               let (open, close) = match g.delimiter() {
+                  Delimiter::Parenthesis => { ('(', ')') },
+                  Delimiter::Bracket => { ('[', ']') },
+                  Delimiter::Brace => { ('{', '}') },
+                  Delimiter::None => { return None; },
+               };
+               if !self.source[*at..].starts_with(open) { return None; }
+               *at += 1;
+               self.locate_in(g.stream(), at, out)?;
+               *at = self.skip_comments(*at)?;
+               if !self.source[*at..].starts_with(close) { return None; }
+               *at += 1;
+            },
+            t => {
+               let text = t.to_string();
+               if !self.source[*at..].starts_with(&text) { return None; }
+               *at += text.len();
+            },
+         }
+         out[index] = self.template_range(start, *at);
+      }
+      Some(())
+   }
+   /// The offset of the next token in the translated source, past any whitespace and comments
+   fn skip_comments(&self, mut at: usize) -> Option<usize> {
+      loop {
+         let rest = &self.source[at..];
+         let trimmed = rest.trim_start();
+         at += rest.len() - trimmed.len();
+         if ["///", "//!", "/**", "/*!"].iter().any(|doc| trimmed.starts_with(doc)) {
+            return None;
+         } else if trimmed.starts_with("//") {
+            at += trimmed.find('\n').unwrap_or(trimmed.len());
+         } else if trimmed.starts_with("/*") {
+            let mut depth = 0;
+            let mut i = 0;
+            while i < trimmed.len() {
+               if trimmed[i..].starts_with("/*") { depth += 1; i += 2; }
+               else if trimmed[i..].starts_with("*/") { depth -= 1; i += 2; if depth == 0 { break; } }
+               else { i += trimmed[i..].chars().next().map(|c| c.len_utf8()).unwrap_or(1); }
+            }
+            at += i;
+         } else {
+            return Some(at);
+         }
+      }
+   }
+   /// The template range of a range of the translated source that lies inside of one piece
+   fn template_range(&self, start: usize, end: usize) -> (usize, usize) {
+      match self.map.iter().rposition(|(s,_,_)| *s <= start) {
+         Some(i) => match self.map[i] {
+            (s,t,true) => { (t + (start - s), t + (end - s)) },
+            (_,t,false) => { (t, self.map.get(i + 1).map(|(_,n,_)| *n).unwrap_or(t).max(t)) },
+         },
+         None => { (0, 0) },
+      }
+   }
+   /// The line and column of a byte offset in a string, both counted from one
+   pub fn line_column(s: &str, offset: usize) -> (usize, usize) {
+      let before = &s[..offset.min(s.len())];
+      let line = before.matches('\n').count() + 1;
+      let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+      (line, column)
+   }
+}
@@ -0,0 +1,94 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, Literal, Ident};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Token, Expr, LitStr, bracketed};
+use syn::token::{Bracket};
+
+use crate::core::DashedName;
+
+/// A CSS property, which is skipped if its value is None or its condition is false
+pub struct XhtmlStyleEntry {
+   pub key: String,
+   pub span: Span,
+   pub value: Expr,
+   pub cond: Option<Expr>
+}
+impl Parse for XhtmlStyleEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let (key, span) = if input.peek(LitStr) {
+          let s: LitStr = input.parse()?;
+          let key = s.value();
+          if key.len()==0 || !key.chars().all(|c| c.is_ascii_alphanumeric() || c=='-' || c=='_') {
+             return Err(Error::new(s.span(), format!("Invalid CSS property name {:?}", key)));
+          }
+          (key, s.span())
+       } else {
+          let n: DashedName = input.parse()?;
+          (n.name, n.span)
+       };
+       let _colon: Token![:] = input.parse()?;
+       let value: Expr = input.parse()?;
+       let cond = if input.peek(Token![if]) {
+          let _if: Token![if] = input.parse()?;
+          Some(input.parse()?)
+       } else { None };
+       Ok(XhtmlStyleEntry { key: key, span: span, value: value, cond: cond })
+    }
+}
+
+/// A style attribute value like style=[ position: "absolute", top: y if shown, "z-index": z ]
+///
+/// Each value may be any Display value, or an Option of one. Values are escaped as CSS, and
+/// a list with no remaining entries leaves out the attribute.
+pub struct XhtmlStyleList {
+   pub bracket: Bracket,
+   pub entries: Vec<XhtmlStyleEntry>
+}
+impl XhtmlStyleList {
+    /// Whether the input is a bracketed list whose first entry has a property name
+    pub fn peek(input: ParseStream) -> bool {
+       fn keyed(input: ParseStream) -> Result<bool> {
+          let content;
+          bracketed!(content in input);
+          if content.peek(LitStr) {
+             let _s: LitStr = content.parse()?;
+          } else {
+             let _n: DashedName = content.parse()?;
+          }
+          Ok(content.peek(Token![:]) && !content.peek(Token![::]))
+       }
+       keyed(&input.fork()).unwrap_or(false)
+    }
+    /// Code that builds the declarations into the StyleList named `list`
+    pub fn build(&self, list: &Ident) -> proc_macro2::TokenStream {
+       let mut ts = proc_macro2::TokenStream::new();
+       for s in self.entries.iter() {
+          let k = Literal::string(&s.key);
+          let v = &s.value;
+          let push = quote_spanned!{s.span=>
+             ::rdxl::interpolate::Style(&(#v)).push_to(#k, &mut #list);
+          };
+          match &s.cond {
+             Some(c) => { (quote_spanned!{s.span=> if #c { #push } }).to_tokens(&mut ts); }
+             None => { push.to_tokens(&mut ts); }
+          }
+       }
+       ts
+    }
+}
+impl Parse for XhtmlStyleList {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let content;
+       let bracket = bracketed!(content in input);
+       let entries = content.parse_terminated::<XhtmlStyleEntry,Token![,]>(XhtmlStyleEntry::parse)?;
+       Ok(XhtmlStyleList {
+          bracket: bracket,
+          entries: entries.into_iter().collect()
+       })
+    }
+}
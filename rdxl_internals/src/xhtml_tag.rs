@@ -0,0 +1,458 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Ident, Token, Expr, LitStr, braced};
+use syn::token::{Brace};
+
+use crate::xhtml::{XhtmlAttr,Xhtml};
+use crate::xhtml_expr::XhtmlExprInner;
+use crate::core::{TokenAsLiteral,DashedName,Emitter,Mode};
+
+pub enum XhtmlAttrKey {
+   S(String),
+   G(Expr,String),
+   R(Expr)
+}
+
+pub struct XhtmlTag {
+   pub tag: String,
+   pub attrs: Vec<(XhtmlAttrKey,Option<XhtmlAttr>)>,
+   pub inner: Xhtml,
+   pub outer_span: Span,
+   pub outer_span_start: Span,
+   pub outer_span_end: Span,
+   pub inner_span_start: Span,
+   pub inner_span_end: Span,
+}
+
+/// Elements that are displayed as blocks or not at all, where breaking spaces around and inside
+/// of them do not matter. Any other element, including custom elements, may be inline.
+const BLOCK: [&str; 54] = ["address","article","aside","base","blockquote","body","caption",
+   "col","colgroup","dd","details","dialog","div","dl","dt","fieldset","figcaption","figure",
+   "footer","form","h1","h2","h3","h4","h5","h6","head","header","hgroup","hr","html","legend",
+   "li","link","main","menu","meta","nav","ol","optgroup","option","p","pre","section","summary",
+   "table","tbody","td","tfoot","th","thead","title","tr","ul"];
+
+/// Elements that never have content, and have no end tag in html5
+const VOID: [&str; 14] = ["area","base","br","col","embed","hr","img",
+   "input","link","meta","param","source","track","wbr"];
+
+/// Attributes whose presence alone means true, so their value can be left out
+const BOOLEAN: [&str; 25] = ["allowfullscreen","async","autofocus","autoplay","checked",
+   "controls","default","defer","disabled","formnovalidate","hidden","inert","ismap","itemscope",
+   "loop","multiple","muted","nomodule","novalidate","open","playsinline","readonly","required",
+   "reversed","selected"];
+
+impl XhtmlTag {
+    pub fn is_block(&self) -> bool {
+        BLOCK.iter().any(|t| (&self.tag)==t)
+    }
+    pub fn is_void(&self) -> bool {
+        VOID.iter().any(|t| (&self.tag)==t)
+    }
+}
+
+impl XhtmlAttr {
+    fn is_boolean(k: &str) -> bool {
+        BOOLEAN.iter().any(|b| k.eq_ignore_ascii_case(b))
+    }
+    /// The shortest form of a static attribute: a boolean attribute has no value, and
+    /// the quotes are left off of a value that HTML5 allows to be unquoted
+    fn minify(k: &str, s: &str) -> String {
+        let v = match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+           Some(v) if !v.contains('\\') => { v },
+           _ => { s },
+        };
+        if XhtmlAttr::is_boolean(k) && (v.is_empty() || v.eq_ignore_ascii_case(k)) {
+           format!(" {}", k)
+        } else if v.is_empty() || v.chars().any(|c| c.is_whitespace() || "\"'=<>`".contains(c)) {
+           format!(" {}={}", k, s)
+        } else {
+           format!(" {}={}", k, v)
+        }
+    }
+    /// The text of a static value, without the quotes and escapes of its literal
+    fn value(s: &str) -> String {
+        if s.starts_with('"') {
+           syn::parse_str::<LitStr>(s).map(|l| l.value()).unwrap_or_else(|_| s.to_string())
+        } else if s.starts_with('\'') && s.len() > 1 {
+           s[1..s.len()-1].to_string()
+        } else {
+           s.to_string()
+        }
+    }
+    /// An attribute without a value, which xml writes with its own name as the value
+    fn flag(k: &str, mode: Mode) -> String {
+        if mode == Mode::Xml { format!(" {}=\"{}\"", k, k) } else { format!(" {}", k) }
+    }
+    fn emit_value(&self, k: &str, span: Span, e: &mut Emitter) {
+        match self {
+           XhtmlAttr::S(s) if e.mode() == Mode::Minify => {
+              e.push_str(&XhtmlAttr::minify(k, s), span);
+           },
+           XhtmlAttr::S(s) if e.mode() == Mode::Xml => {
+              e.push_str(&format!(" {}=\"", k), span);
+              e.attribute(|e| e.push_text(&XhtmlAttr::value(s), span));
+              e.push_str("\"", span);
+           },
+           XhtmlAttr::S(s) => {
+              e.push_str(&format!(" {}={}", k, s), span);
+           }, XhtmlAttr::F(f) if e.mode() == Mode::Xml => {
+              let v = e.nested(|e| f.emit(e));
+              e.push_str(&format!(" {}=\"", k), span);
+              e.push_code(quote_spanned!{span=>
+                 let _ = ::rdxl::escape::xml_attribute(&mut stream, &{
+                   let mut stream = String::new();
+                   #v
+                   stream
+                 });
+              });
+              e.push_str("\"", span);
+           }, XhtmlAttr::F(f) => {
+              let v = e.nested(|e| f.emit(e));
+              e.push_str(&format!(" {}=\"", k), span);
+              e.push_code(quote_spanned!{span=>
+                 stream.push_str(&{
+                   let mut stream = String::new();
+                   #v
+                   stream.replace('"', "&quot;")
+                 });
+              });
+              e.push_str("\"", span);
+           }, XhtmlAttr::L(l) => {
+              //an empty list leaves out the attribute
+              //the list is hidden from the user's expressions, which may use the same name
+              let list = Ident::new("list", Span::mixed_site());
+              let build = l.build(&list);
+              let open = e.nested(|e| e.push_str(&format!(" {}=\"", k), span));
+              let close = e.nested(|e| e.push_str("\"", span));
+              e.push_code(quote_spanned!{span=>
+                 {
+                    #[allow(unused_imports)]
+                    use ::rdxl::interpolate::{IterTokens as _, DisplayTokens as _, IteratorTokens as _};
+                    #[allow(unused_mut)]
+                    let mut #list = ::rdxl::interpolate::TokenList::new();
+                    #build
+                    if !#list.is_empty() {
+                       #open
+                       #list.write_to(&mut stream);
+                       #close
+                    }
+                 }
+              });
+           }, XhtmlAttr::C(c) => {
+              //properties that are None are skipped, and an empty style leaves out the attribute
+              let list = Ident::new("list", Span::mixed_site());
+              let build = c.build(&list);
+              let open = e.nested(|e| e.push_str(&format!(" {}=\"", k), span));
+              let close = e.nested(|e| e.push_str("\"", span));
+              e.push_code(quote_spanned!{span=>
+                 {
+                    #[allow(unused_imports)]
+                    use ::rdxl::interpolate::{OptionStyle as _, DisplayStyle as _};
+                    #[allow(unused_mut)]
+                    let mut #list = ::rdxl::interpolate::StyleList::new();
+                    #build
+                    if !#list.is_empty() {
+                       #open
+                       #list.write_to(&mut stream);
+                       #close
+                    }
+                 }
+              });
+           }, XhtmlAttr::E(x) => {
+              if let XhtmlExprInner::E(v) = &x.expr {
+                 //Option values may leave out the attribute, and bool values of boolean attributes its value
+                 let boolean = XhtmlAttr::is_boolean(k);
+                 let flag = e.nested(|e| e.push_str(&XhtmlAttr::flag(k, e.mode()), span));
+                 let open = e.nested(|e| e.push_str(&format!(" {}=\"", k), span));
+                 let close = e.nested(|e| e.push_str("\"", span));
+                 let value = if e.mode() == Mode::Xml {
+                    quote_spanned!{span=>
+                       (&::rdxl::interpolate::Interpolate(v)).write_xml_attr(&mut stream);
+                    }
+                 } else {
+                    quote_spanned!{span=>
+                       stream.push_str(&{
+                         let mut stream = String::new();
+                         (&::rdxl::interpolate::Interpolate(v)).write_text(&mut stream);
+                         stream.replace('"', "&quot;")
+                       });
+                    }
+                 };
+                 e.push_code(quote_spanned!{span=>
+                    {
+                       #[allow(unused_imports)]
+                       use ::rdxl::interpolate::{FlagAttr as _, OptionAttr as _, PlainAttr as _, RawInterpolate as _, EscapedInterpolate as _};
+                       match (&&::rdxl::interpolate::Attr(&(#v), #boolean)).attr_value() {
+                          ::rdxl::interpolate::AttrValue::Omit => {},
+                          ::rdxl::interpolate::AttrValue::Flag => { #flag },
+                          ::rdxl::interpolate::AttrValue::Value(v) => {
+                             #open
+                             #value
+                             #close
+                          },
+                       }
+                    }
+                 });
+              } else if e.mode() == Mode::Xml {
+                 e.push_str(&format!(" {}=\"", k), span);
+                 e.attribute(|e| x.emit(e));
+                 e.push_str("\"", span);
+              } else {
+                 let v = e.nested(|e| x.emit(e));
+                 e.push_str(&format!(" {}=\"", k), span);
+                 e.push_code(quote_spanned!{span=>
+                    stream.push_str(&{
+                      let mut stream = String::new();
+                      #v
+                      stream.replace('"', "&quot;")
+                    });
+                 });
+                 e.push_str("\"", span);
+              }
+           }
+        }
+    }
+}
+
+impl XhtmlTag {
+    fn emit_attr(&self, k: &XhtmlAttrKey, v: &Option<XhtmlAttr>, e: &mut Emitter) {
+        let span = self.outer_span;
+        match (k,v) {
+           (XhtmlAttrKey::S(k),None) => {
+              e.push_str(&XhtmlAttr::flag(k, e.mode()), span);
+           }, (XhtmlAttrKey::S(k),Some(v)) => {
+              v.emit_value(k, span, e);
+           }, (XhtmlAttrKey::G(g,k),v) => {
+              let a = e.nested(|e| match v {
+                 None => { e.push_str(&XhtmlAttr::flag(k, e.mode()), span); }
+                 Some(v) => { v.emit_value(k, span, e); }
+              });
+              e.push_code(quote_spanned!{span=>
+                 if #g { #a }
+              });
+           }, (XhtmlAttrKey::R(r),_) => {
+              let attrs = Ident::new("attrs", Span::mixed_site());
+              let spread = Ident::new(if e.mode() == Mode::Xml { "spread_xml" } else { "spread" }, span);
+              e.push_code(quote_spanned!{span=>
+                 for (k,v) in #r {
+                    #attrs.#spread(k, v);
+                 }
+              });
+           }
+        }
+    }
+    /// Spread attributes are collected with the others, so that a later attribute
+    /// replaces an earlier attribute of the same name
+    fn emit_spread_attrs(&self, e: &mut Emitter) {
+        let span = self.outer_span;
+        let attrs = Ident::new("attrs", Span::mixed_site());
+        let mut ts = proc_macro2::TokenStream::new();
+        for (k,v) in self.attrs.iter() {
+           let a = e.nested(|e| self.emit_attr(k, v, e));
+           let name = match k {
+              XhtmlAttrKey::S(k) | XhtmlAttrKey::G(_,k) => { k }
+              XhtmlAttrKey::R(_) => { ts.extend(a); continue; }
+           };
+           ts.extend(quote_spanned!{span=>
+              {
+                 let mut stream = String::new();
+                 #a
+                 #attrs.set(#name, stream);
+              }
+           });
+        }
+        e.push_code(quote_spanned!{span=>
+           {
+              let mut #attrs = ::rdxl::interpolate::AttrList::new();
+              #ts
+              #attrs.write_to(&mut stream);
+           }
+        });
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+        let span = self.outer_span;
+        e.push_str(&format!("<{}", self.tag), span);
+
+        if self.attrs.iter().any(|(k,_)| matches!(k, XhtmlAttrKey::R(_))) {
+           self.emit_spread_attrs(e);
+        } else {
+           for (k,v) in self.attrs.iter() {
+              self.emit_attr(k, v, e);
+           }
+        }
+
+        let self_closing = ["area","base","br","embed","hr","iframe","img",
+           "input","link","meta","param","source","track"];
+        if e.mode() == Mode::Html5 && self.is_void() {
+           e.push_str(">", span);
+           if self.inner.crumbs.len()>0 {
+              let msg = format!("{} is a void element and cannot have content", self.tag);
+              e.push_code(Error::new(self.inner.span(), msg).to_compile_error());
+           }
+        } else if e.mode() == Mode::Html5 {
+           e.push_str(">", span);
+           self.emit_inner(e);
+        } else if e.mode() == Mode::Minify {
+           //minified output is html5, where an unquoted value would take the slash of /> as its last character
+           e.push_str(">", span);
+           if self.inner.crumbs.len()>0 || !self.is_void() {
+              self.emit_inner(e);
+           }
+        } else if self.inner.crumbs.len()==0 && (e.mode() == Mode::Xml || self_closing.iter().any(|s| (&self.tag)==s)) {
+           e.push_str("/>", span);
+        } else {
+           e.push_str(">", span);
+           self.emit_inner(e);
+        }
+    }
+    /// Emits the content and the end tag
+    fn emit_inner(&self, e: &mut Emitter) {
+        let span = self.outer_span;
+
+        //when minified, the ends of a block element have no breaking spaces
+        let minify = e.mode() == Mode::Minify && self.is_block();
+        if self.inner.crumbs.len()>0 && self.inner.start() > self.inner_span_start.end() && !self.inner.trims_left() && !minify {
+           e.push_space(span);
+        }
+
+        self.inner.emit(e);
+
+        if self.inner.crumbs.len()>0 && self.inner.end() < self.inner_span_end.start() && !self.inner.trims_right() && !minify {
+           e.push_space(span);
+        }
+
+        e.push_str(&format!("</{}>", self.tag), span);
+    }
+}
+
+impl Parse for XhtmlTag {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let l1: Token![<] = input.parse()?;
+        let t = DashedName::parse_prefixed(input)?;
+
+        let mut attrs: Vec<(XhtmlAttrKey,Option<XhtmlAttr>)> = Vec::new();
+        while input.peek(Ident) ||
+              input.peek(LitStr) ||
+              input.peek(Token![as]) ||
+              input.peek(Token![break]) ||
+              input.peek(Token![const]) ||
+              input.peek(Token![continue]) ||
+              input.peek(Token![crate]) ||
+              input.peek(Token![else]) ||
+              input.peek(Token![enum]) ||
+              input.peek(Token![extern]) ||
+              input.peek(Token![fn]) ||
+              input.peek(Token![for]) ||
+              input.peek(Token![if]) ||
+              input.peek(Token![impl]) ||
+              input.peek(Token![in]) ||
+              input.peek(Token![let]) ||
+              input.peek(Token![loop]) ||
+              input.peek(Token![match]) ||
+              input.peek(Token![mod]) ||
+              input.peek(Token![move]) ||
+              input.peek(Token![mut]) ||
+              input.peek(Token![pub]) ||
+              input.peek(Token![ref]) ||
+              input.peek(Token![return]) ||
+              input.peek(Token![self]) ||
+              input.peek(Token![Self]) ||
+              input.peek(Token![static]) ||
+              input.peek(Token![struct]) ||
+              input.peek(Token![super]) ||
+              input.peek(Token![trait]) ||
+              input.peek(Token![type]) ||
+              input.peek(Token![unsafe]) ||
+              input.peek(Token![use]) ||
+              input.peek(Token![where]) ||
+              input.peek(Token![while]) ||
+              input.peek(Brace) {
+            if input.peek(Brace) {
+               let content1;
+               let content2;
+               let _brace1: Brace = braced!(content1 in input);
+               let _brace2: Brace = braced!(content2 in content1);
+               if content2.peek(Token![..]) {
+                  let _dots: Token![..] = content2.parse()?;
+                  let expr: Expr = content2.parse()?;
+                  attrs.push(( XhtmlAttrKey::R(expr), None ));
+                  continue;
+               }
+               let _if: Token![if] = content2.parse()?;
+               let expr: Expr = content2.call(Expr::parse_without_eager_brace)?;
+
+               let content3;
+               let content4;
+               let _brace3: Brace = braced!(content3 in content2);
+               let _brace4: Brace = braced!(content4 in content3);
+               let key = if content4.peek(LitStr) { let s:LitStr = content4.parse()?; s.value()
+                         } else { DashedName::parse_prefixed(&content4)?.name };
+               let v = if content4.peek(Token![=]) {
+                  let _eq: Token![=] = content4.parse()?;
+                  let attr_expr: XhtmlAttr = XhtmlAttr::parse(&content4, key.clone())?;
+                  Some(attr_expr)
+               } else { None };
+               attrs.push(( XhtmlAttrKey::G(expr,key), v ));
+            } else {
+               let key = if input.peek(LitStr) { let t: TokenAsLiteral = input.parse()?; t.token_literal
+                         } else { DashedName::parse_prefixed(input)?.name };
+               let v = if input.peek(Token![=]) {
+                  let _eq: Token![=] = input.parse()?;
+                  let attr_expr: XhtmlAttr = XhtmlAttr::parse(input, key.clone())?;
+                  Some(attr_expr)
+               } else { None };
+               attrs.push(( XhtmlAttrKey::S(key), v ));
+           }
+        }
+
+        if input.peek(Token![/]) {
+           let r1: Token![/] = input.parse()?;
+           let r2: Token![>] = input.parse()?;
+
+           Ok(XhtmlTag {
+              tag: t.name.clone(),
+              attrs: attrs,
+              inner: Xhtml { crumbs: vec!() },
+              outer_span: l1.span.join(r2.span).unwrap_or(l1.span),
+              outer_span_start: l1.span.clone(),
+              outer_span_end: r2.span.clone(),
+              inner_span_start: r1.span.clone(),
+              inner_span_end: r2.span.clone(),
+           })
+        } else {
+           let l2: Token![>] = input.parse()?;
+
+           let inner: Xhtml = input.parse()?;
+
+           let r1: Token![<] = input.parse()?;
+           let _r2: Token![/] = input.parse()?;
+           let t2 = DashedName::parse_prefixed(input)?;
+           if t.name != t2.name {
+              let msg = format!("Expected </{}> found </{}>", t.name, t2.name);
+              let r = Error::new(t2.span, msg);
+              return Err(r)
+           }
+           let r3: Token![>] = input.parse()?;
+        
+           Ok(XhtmlTag {
+              tag: t.name.clone(),
+              attrs: attrs,
+              inner: inner,
+              outer_span: l1.span.join(r3.span).unwrap_or(l1.span),
+              outer_span_start: l1.span.clone(),
+              outer_span_end: r3.span.clone(),
+              inner_span_start: l2.span.clone(),
+              inner_span_end: r1.span.clone(),
+           })
+       }
+    }
+}
+
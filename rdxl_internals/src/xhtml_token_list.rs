@@ -0,0 +1,79 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Literal, Ident};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token, Expr, LitStr, bracketed};
+use syn::token::{Bracket};
+
+pub enum XhtmlTokenValue {
+   S(LitStr),
+   E(Expr)
+}
+
+/// An entry of a token list, which is skipped if its condition is false
+pub struct XhtmlToken {
+   pub value: XhtmlTokenValue,
+   pub cond: Option<Expr>
+}
+impl Parse for XhtmlToken {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let value = if input.peek(LitStr) {
+          XhtmlTokenValue::S(input.parse()?)
+       } else {
+          XhtmlTokenValue::E(input.parse()?)
+       };
+       let cond = if input.peek(Token![if]) {
+          let _if: Token![if] = input.parse()?;
+          Some(input.parse()?)
+       } else { None };
+       Ok(XhtmlToken { value: value, cond: cond })
+    }
+}
+
+/// A space separated attribute value like class=[ "btn", "active" if selected, extra ]
+///
+/// String literals are written as is, and expressions may be any Display value or any
+/// collection or iterator of Display values. Expressions are borrowed, so a collection
+/// can be a field of a component or be used again in a loop. Duplicates are removed and an empty list leaves out the attribute.
+pub struct XhtmlTokenList {
+   pub bracket: Bracket,
+   pub tokens: Vec<XhtmlToken>
+}
+impl XhtmlTokenList {
+    /// Code that builds the list into the TokenList named `list`
+    pub fn build(&self, list: &Ident) -> proc_macro2::TokenStream {
+       let mut ts = proc_macro2::TokenStream::new();
+       for t in self.tokens.iter() {
+          let push = match &t.value {
+             XhtmlTokenValue::S(s) => {
+                let l = Literal::string(&s.value());
+                quote_spanned!{s.span()=> #list.push_str(#l); }
+             }, XhtmlTokenValue::E(e) => {
+                quote_spanned!{syn::spanned::Spanned::span(e)=>
+                   (&&::rdxl::interpolate::Tokens(&(#e))).push_to(&mut #list);
+                }
+             }
+          };
+          match &t.cond {
+             Some(c) => { (quote_spanned!{self.bracket.span=> if #c { #push } }).to_tokens(&mut ts); }
+             None => { push.to_tokens(&mut ts); }
+          }
+       }
+       ts
+    }
+}
+impl Parse for XhtmlTokenList {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let content;
+       let bracket = bracketed!(content in input);
+       let tokens = content.parse_terminated::<XhtmlToken,Token![,]>(XhtmlToken::parse)?;
+       Ok(XhtmlTokenList {
+          bracket: bracket,
+          tokens: tokens.into_iter().collect()
+       })
+    }
+}
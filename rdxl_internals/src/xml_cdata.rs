@@ -0,0 +1,115 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, Delimiter};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{bracketed, Ident, LitStr, Token};
+
+use crate::core::Emitter;
+use crate::xhtml::XhtmlExpr;
+use crate::xhtml_expr::XhtmlExprInner;
+
+/// A CDATA section, like <![CDATA["a < b" {{ x }}]]>
+///
+/// The content is a sequence of string literals and expressions, which are written
+/// without escaping, except that a `]]>` is split so that it cannot end the section.
+pub struct XmlCdata {
+   pub open: Token![<],
+   pub parts: Vec<XmlCdataPart>,
+   pub close: Token![>]
+}
+
+pub enum XmlCdataPart {
+   S(LitStr),
+   E(XhtmlExpr),
+}
+
+impl XmlCdata {
+    pub fn peek(input: ParseStream) -> bool {
+       let cursor = input.cursor();
+       let cdata = cursor.punct().filter(|(p,_)| p.as_char() == '<')
+          .and_then(|(_,rest)| rest.punct()).filter(|(p,_)| p.as_char() == '!')
+          .and_then(|(_,rest)| rest.group(Delimiter::Bracket))
+          .and_then(|(inner,_,_)| inner.ident());
+       matches!(cdata, Some((i,_)) if i == "CDATA")
+    }
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+       let span = self.span();
+       //content without expressions is split at compile time
+       if self.parts.iter().all(|p| matches!(p, XmlCdataPart::S(_))) {
+          let cdata = self.parts.iter().map(|p| match p {
+             XmlCdataPart::S(s) => { s.value() },
+             XmlCdataPart::E(_) => { String::new() },
+          }).collect::<String>();
+          e.push_str(&format!("<![CDATA[{}]]>", cdata.replace("]]>", "]]]]><![CDATA[>")), span);
+          return;
+       }
+       let mut parts = proc_macro2::TokenStream::new();
+       for p in self.parts.iter() {
+          match p {
+             XmlCdataPart::S(s) => {
+                (quote_spanned!{s.span()=>
+                   cdata.push_str(#s);
+                }).to_tokens(&mut parts);
+             },
+             XmlCdataPart::E(x) => {
+                if let XhtmlExprInner::E(x) = &x.expr {
+                   (quote_spanned!{syn::spanned::Spanned::span(x)=>
+                      let _ = ::std::fmt::Write::write_fmt(&mut cdata, format_args!("{}", #x));
+                   }).to_tokens(&mut parts);
+                }
+             }
+          }
+       }
+       e.push_str("<![CDATA[", span);
+       e.push_code(quote_spanned!{span=>
+          {
+             let mut cdata = String::new();
+             #parts
+             let _ = ::rdxl::escape::cdata(&mut stream, &cdata);
+          }
+       });
+       e.push_str("]]>", span);
+    }
+}
+
+impl Parse for XmlCdata {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _: Token![!] = input.parse()?;
+       let outer;
+       bracketed!(outer in input);
+       let _: Ident = outer.parse()?;
+       let content;
+       bracketed!(content in outer);
+
+       let mut parts = Vec::new();
+       while !content.is_empty() {
+          if content.peek(LitStr) {
+             parts.push(XmlCdataPart::S(content.parse()?));
+          } else {
+             let x: XhtmlExpr = content.parse().map_err(|err| {
+                Error::new(err.span(), "CDATA content must be string literals and {{ expressions }}")
+             })?;
+             if !matches!(x.expr, XhtmlExprInner::E(_)) {
+                return Err(Error::new(x.brace_token1.span, "CDATA content must be string literals and {{ expressions }}"));
+             }
+             parts.push(XmlCdataPart::E(x));
+          }
+       }
+
+       let close: Token![>] = input.parse()?;
+
+       Ok(XmlCdata {
+          open: open,
+          parts: parts,
+          close: close,
+       })
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{LitStr, Token};
+
+use crate::core::{Emitter,DashedName};
+
+/// An xml declaration or processing instruction, like <?xml version="1.0"?>
+///
+/// The content is a list of pseudo-attributes with literal values.
+pub struct XmlInstruction {
+   pub open: Token![<],
+   pub target: String,
+   pub attrs: Vec<(String,LitStr)>,
+   pub close: Token![>]
+}
+
+impl XmlInstruction {
+    pub fn peek(input: ParseStream) -> bool {
+       input.peek(Token![<]) && input.peek2(Token![?])
+    }
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    pub fn emit(&self, e: &mut Emitter) {
+       let mut s = format!("<?{}", self.target);
+       for (k,v) in self.attrs.iter() {
+          s.push_str(&format!(" {}=\"{}\"", k, v.value().replace('&', "&amp;").replace('"', "&quot;")));
+       }
+       s.push_str("?>");
+       e.push_str(&s, self.span());
+    }
+}
+
+impl Parse for XmlInstruction {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _: Token![?] = input.parse()?;
+       let target: DashedName = input.parse()?;
+
+       let mut attrs = Vec::new();
+       while !input.peek(Token![?]) {
+          if input.is_empty() {
+             return Err(Error::new(target.span, format!("unterminated processing instruction {}", target.name)));
+          }
+          let k = DashedName::parse_prefixed(input)?;
+          let _: Token![=] = input.parse()?;
+          let v: LitStr = input.parse().map_err(|err| {
+             Error::new(err.span(), "processing instruction values must be string literals")
+          })?;
+          attrs.push((k.name, v));
+       }
+
+       let _: Token![?] = input.parse()?;
+       let close: Token![>] = input.parse()?;
+
+       Ok(XmlInstruction {
+          open: open,
+          target: target.name,
+          attrs: attrs,
+          close: close,
+       })
+    }
+}
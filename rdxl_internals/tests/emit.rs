@@ -0,0 +1,30 @@
+use rdxl_internals::xhtml::Xhtml;
+use rdxl_internals::core::Mode;
+use quote::ToTokens;
+use syn::parse_quote;
+
+fn pushes(x: &Xhtml, mode: Mode) -> usize {
+   x.render(mode).to_token_stream().to_string().matches("stream . push").count()
+}
+
+#[test]
+fn coalesce1() {
+   let x: Xhtml = parse_quote! { <div class="a"><b>bold</b><br/></div> };
+   assert_eq!(pushes(&x, Mode::Xhtml), 1);
+   assert_eq!(x.render(Mode::Xhtml).capacity(), r#"<div class="a"><b>bold</b><br/></div>"#.len());
+}
+
+#[test]
+fn coalesce2() {
+   let x: Xhtml = parse_quote! { <ul>{{ for i in 0..3 {{ <li>{{ i }}</li> }} }}</ul> };
+   //"<ul>", then "<li>" and "</li> " around the interpolation, then "</ul>"
+   assert_eq!(pushes(&x, Mode::Xhtml), 4);
+}
+
+#[test]
+fn coalesce3() {
+   let x: Xhtml = parse_quote! { <div class="a"><b>bold</b><br/></div> };
+   let unmerged = x.render_unmerged(Mode::Xhtml);
+   assert!(unmerged.to_token_stream().to_string().matches("stream . push").count() > 1);
+   assert_eq!(unmerged.capacity(), x.render(Mode::Xhtml).capacity());
+}
@@ -0,0 +1,77 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use quote::{quote};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{LitStr};
+
+/// The path of a template file, relative to the manifest directory of the crate being compiled
+pub struct IncludeXhtml {
+   pub path: LitStr
+}
+impl Parse for IncludeXhtml {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(IncludeXhtml {
+            path: input.parse()?
+        })
+    }
+}
+
+impl IncludeXhtml {
+    pub fn full_path(&self) -> Result<PathBuf> {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| {
+            Error::new(self.path.span(), format!("Could not read CARGO_MANIFEST_DIR: {}", e))
+        })?;
+        Ok(PathBuf::from(dir).join(self.path.value()))
+    }
+
+    /// Writes a copy of the template wrapped in a call to xhtml! and returns its path
+    ///
+    /// The copy keeps the lines of the template and its file name, and is named after a hash of
+    /// both the path and the contents, so an existing copy is never rewritten.
+    fn wrap(&self, path: &Path, source: &str) -> Result<PathBuf> {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        source.hash(&mut hasher);
+        let dir = std::env::temp_dir().join("rdxl").join(format!("{:016x}", hasher.finish()));
+        let wrapped = dir.join(path.file_name().unwrap_or_default());
+        if wrapped.exists() { return Ok(wrapped); }
+
+        let failed = |e: std::io::Error| {
+            Error::new(self.path.span(), format!("Could not write {}: {}", wrapped.display(), e))
+        };
+        std::fs::create_dir_all(&dir).map_err(failed)?;
+        //another build may be writing the same copy, so it is moved into place whole
+        let partial = dir.join(format!(".{}", std::process::id()));
+        std::fs::write(&partial, format!("::rdxl::xhtml!{{{}\n}}\n", source)).map_err(failed)?;
+        std::fs::rename(&partial, &wrapped).map_err(failed)?;
+        Ok(wrapped)
+    }
+
+    /// Renders the template file the same way as xhtml!
+    ///
+    /// The compiler includes a copy of the file that calls xhtml! on its contents, so every token
+    /// has its line and column in the copy and any error, including a type error in the Rust code,
+    /// points at the line of the template where it was written.
+    pub fn expand(&self) -> Result<proc_macro::TokenStream> {
+        let path = self.full_path()?;
+        let source = std::fs::read_to_string(&path).map_err(|e| {
+            Error::new(self.path.span(), format!("Could not read {}: {}", path.display(), e))
+        })?;
+        let wrapped = self.wrap(&path, &source)?.display().to_string();
+
+        //including the file as a string makes cargo rebuild when it changes
+        let path = path.display().to_string();
+        Ok(proc_macro::TokenStream::from(quote! {
+            {
+                const _: &str = include_str!(#path);
+                include!(#wrapped)
+            }
+        }))
+    }
+}
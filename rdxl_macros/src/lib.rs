@@ -0,0 +1,762 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Procedural macros for [rdxl](https://docs.rs/rdxl/). These are re-exported from the rdxl crate,
+//! which should be used instead of depending on this crate directly.
+
+#![recursion_limit = "128"]
+#![crate_type = "proc-macro"]
+#![allow(clippy::redundant_field_names, clippy::cmp_owned)]
+
+mod xhtml;
+mod xtype;
+mod xrender;
+mod xlayout;
+mod xtextrender;
+mod xhtml_to;
+mod include_xhtml;
+mod xhtml_str;
+
+use proc_macro::{TokenStream};
+use syn::{parse_macro_input};
+use quote::{quote};
+use rdxl_internals::core::{Emitter,Mode};
+
+/// The [xhtml!](https://andrew-johnson-4.github.io/rdxl) macro is the primary mechanism for templating in rdxl
+///
+/// <b>xhtml!</b> consumes mixed Rust code and XML markup as input and emits rendered xhtml to a string buffer.
+/// Rust code is usually delimited by {{double braces}} or [[double brackets]]. The <b>syn</b> module is used to
+/// allow most Rust expressions to be used inside the correct delimited contexts. Control flow structures
+/// such as if/else blocks, loops, and let statements may be used inline as well.
+///
+/// Aside from standard XML syntax, custom types may be defined with <b>xtype!</b> and <b>xrender!</b> facilities. This
+/// encourages typesafe modular templates to be created and shared.
+///
+/// Use of <b>xhtml!</b> usually looks something like this:
+/// ```
+/// # use rdxl::xhtml;
+/// # fn main() {
+/// let mut x = 5;
+///
+/// println!("{}",xhtml!(<div>
+///    {{ x }},
+///    {{ x = 3; }}
+///    {{ x }},
+///    {{ x = 7; }}
+///    {{ x }},
+///    {{ let mut y = 2 }}
+///    {{ y }},
+///    {{ y = 1; }}
+///    {{ y }}
+///    {{ for i in (0..x) {{
+///       <span>{{i}}</span>
+///    }} }}
+/// </div>));
+/// # }
+/// ```
+#[proc_macro]
+pub fn xhtml(input: TokenStream) -> TokenStream {
+    let xhtmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Xhtml);
+    let capacity = xhtmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xhtmls
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Renders xhtml like <b>xhtml!</b>, but with a push_str for each piece of static text
+///
+/// This is only used by the profile_runtime benchmark to measure what merging static text saves.
+#[doc(hidden)]
+#[proc_macro]
+pub fn xhtml_unmerged(input: TokenStream) -> TokenStream {
+    let xhtmls = parse_macro_input!(input as xhtml::Xhtml).render_unmerged(Mode::Xhtml);
+    let capacity = xhtmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xhtmls
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [html!](https://andrew-johnson-4.github.io/rdxl) macro renders html5
+///
+/// <b>html!</b> takes the same input as <b>xhtml!</b>, and serializes it as html5 rather than xhtml.
+/// Void elements such as br and img are written without a closing slash, and content inside
+/// of them is a compile error. Other elements always get an end tag, even when they are empty.
+/// A template that starts with an html element is preceded by `<!DOCTYPE html>`.
+///
+/// ```
+/// # use rdxl::html;
+/// # fn main() {
+/// assert_eq!(html!(<html><body><br/><script async src="a.js"/></body></html>),
+///    "<!DOCTYPE html><html><body><br><script async src=\"a.js\"></script></body></html>");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use rdxl::html;
+/// # fn main() {
+/// html!(<img src="a.png">caption</img>);
+/// # }
+/// ```
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let htmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Html5);
+    let capacity = htmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #htmls
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xml!](https://andrew-johnson-4.github.io/rdxl) macro renders xml
+///
+/// <b>xml!</b> takes the same input as <b>xhtml!</b>, and also accepts namespace prefixed
+/// names like atom:link, xml declarations and processing instructions like
+/// `<?xml version="1.0"?>`, and CDATA sections like `<![CDATA["a < b" {{ x }}]]>`.
+/// Empty elements close themselves, attributes without a value are written with
+/// their own name as the value, and text and attribute values are escaped with xml rules,
+/// whether they are written in the template or interpolated.
+///
+/// ```
+/// # use rdxl::xml;
+/// # fn main() {
+/// let title = "Tom & Jerry";
+/// assert_eq!(xml!(
+///    <?xml version="1.0" encoding="utf-8"?>
+///    <feed xmlns="http://www.w3.org/2005/Atom" xmlns:atom="http://www.w3.org/2005/Atom">
+///       <title>{{ title }}</title>
+///       <atom:link href="http://example.com/feed" rel="self"></atom:link>
+///    </feed>
+/// ), "<?xml version=\"1.0\" encoding=\"utf-8\"?> <feed xmlns=\"http://www.w3.org/2005/Atom\" \
+///     xmlns:atom=\"http://www.w3.org/2005/Atom\"> <title>Tom &amp; Jerry</title> \
+///     <atom:link href=\"http://example.com/feed\" rel=\"self\"/> </feed>");
+/// # }
+/// ```
+#[proc_macro]
+pub fn xml(input: TokenStream) -> TokenStream {
+    let xmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Xml);
+    let capacity = xmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xmls
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xhtml_min!](https://andrew-johnson-4.github.io/rdxl) macro renders minified xhtml
+///
+/// <b>xhtml_min!</b> takes the same input as <b>xhtml!</b>, and minifies the static parts of the
+/// template while it is expanded, so there is no cost at runtime. Breaking spaces next to block
+/// elements such as div and p and at the ends of block elements are left out, while those around
+/// any other element, including custom elements, are kept. Static boolean attributes such as
+/// checked and disabled that are empty or have their own name as the value are written without a
+/// value, and static values are unquoted where HTML5 allows.
+/// Void elements such as br end with `>` instead of `/>`. Comments are left out.
+///
+/// ```
+/// # use rdxl::xhtml_min;
+/// # fn main() {
+/// let items = ["a", "b"];
+///
+/// assert_eq!(xhtml_min!(<ul class="list">
+///    {{ for i in items.iter() {{
+///       <li> <input type="checkbox" checked="checked"/> <b>{{ i }}</b> </li>
+///    }} }}
+/// </ul>), "<ul class=list><li><input type=checkbox checked> <b>a</b></li><li><input type=checkbox checked> <b>b</b></li></ul>");
+/// # }
+/// ```
+#[proc_macro]
+pub fn xhtml_min(input: TokenStream) -> TokenStream {
+    let xhtmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Minify);
+    let capacity = xhtmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xhtmls
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xhtml_pretty!](https://andrew-johnson-4.github.io/rdxl) macro renders xhtml with one block element per line
+///
+/// <b>xhtml_pretty!</b> takes the same input as <b>xhtml!</b> and formats the rendered markup with
+/// <b>rdxl::pretty::pretty</b>, which indents nested block elements and keeps text and inline elements
+/// together on a line. This is meant for debugging and for comparing against golden files.
+///
+/// Like <b>xhtml!</b>, the template is read as tokens, so whitespace written inside of
+/// <b>pre</b> or <b>textarea</b> in the template is already collapsed before it is printed.
+/// Whitespace in interpolated values is kept as is.
+///
+/// ```
+/// # use rdxl::xhtml_pretty;
+/// # fn main() {
+/// let items = ["a", "b"];
+///
+/// assert_eq!(xhtml_pretty!(<ul>{{ for i in items.iter() {{ <li><b>{{ i }}</b>!</li> }} }}</ul>),
+///    "<ul>\n  <li><b>a</b>!</li>\n  <li><b>b</b>!</li>\n</ul>\n");
+/// # }
+/// ```
+#[proc_macro]
+pub fn xhtml_pretty(input: TokenStream) -> TokenStream {
+    let xhtmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Xhtml);
+    let capacity = xhtmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xhtmls
+                ::rdxl::pretty::pretty(&stream)
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xhtml_str!](https://andrew-johnson-4.github.io/rdxl) macro renders a template written as real HTML
+///
+/// <b>xhtml_str!</b> takes a string literal, usually a raw string. Everything outside of {{double braces}}
+/// is HTML that is rendered exactly as it was written, so text with apostrophes, unbalanced brackets,
+/// inline CSS and scripts need no quoting. The double braces are Rust code with the same statements
+/// as <b>xhtml!</b>, and the bodies of those statements are HTML again.
+///
+/// Syntax errors in the Rust code are reported with their line and column in the string. Other errors
+/// point at the code itself on a nightly compiler, and at the whole string on a stable one.
+///
+/// ```
+/// # use rdxl::xhtml_str;
+/// # fn main() {
+/// let items = ["don't", "<panic>"];
+///
+/// assert_eq!(xhtml_str!(r#"<style>p { margin: 0 }</style><p>{{ for i in items.iter() {{[{{ i }}]}} }}</p>"#),
+///    "<style>p { margin: 0 }</style><p>[don't][&lt;panic&gt;]</p>");
+/// # }
+/// ```
+#[proc_macro]
+pub fn xhtml_str(input: TokenStream) -> TokenStream {
+    let xhtml_str = parse_macro_input!(input as xhtml_str::XhtmlStrLit);
+    match xhtml_str.expand() {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+/// The [include_xhtml!](https://andrew-johnson-4.github.io/rdxl) macro renders xhtml from a template file
+///
+/// <b>include_xhtml!</b> takes a path relative to the directory of the Cargo.toml of the crate being compiled
+/// and renders the file the same way as <b>xhtml!</b>, with {{double braces}} code in scope at the call site.
+/// The compiler reads a copy of the file that is written to the temporary directory, with the same name
+/// and lines, so every error, whether in the markup or in the Rust code, points at the line where it was
+/// written. The crate is rebuilt when the file changes.
+///
+/// ```
+/// # use rdxl::include_xhtml;
+/// # fn main() {
+/// let title = "Home";
+/// let name = "World";
+/// let items = [1, 2, 3];
+///
+/// let page: String = include_xhtml!("../tests/templates/page.rdxl");
+/// # }
+/// ```
+#[proc_macro]
+pub fn include_xhtml(input: TokenStream) -> TokenStream {
+    let include = parse_macro_input!(input as include_xhtml::IncludeXhtml);
+    match include.expand() {
+        Ok(expanded) => expanded,
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+/// The [xhtml_to!](https://andrew-johnson-4.github.io/rdxl) macro renders xhtml directly into a writer
+///
+/// <b>xhtml_to!</b> takes a writer followed by the same input as <b>xhtml!</b>. The writer may implement
+/// either <b>std::io::Write</b> or <b>std::fmt::Write</b> and may be passed by value or as a mutable reference.
+/// Markup is written as it is rendered, without an intermediate String, and the first write error is returned
+/// as an <b>io::Result</b> or <b>fmt::Result</b> respectively.
+///
+/// ```
+/// # use rdxl::xhtml_to;
+/// # fn main() -> std::io::Result<()> {
+/// let mut out = std::io::BufWriter::new(Vec::new());
+/// xhtml_to!(&mut out, <ul>
+///    {{ for i in 0..3 {{
+///       <li>{{ i }}</li>
+///    }} }}
+/// </ul>)?;
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro]
+pub fn xhtml_to(input: TokenStream) -> TokenStream {
+    let xhtml_to = parse_macro_input!(input as xhtml_to::XhtmlTo);
+
+    let writer = xhtml_to.writer;
+    let xhtmls = xhtml_to.xhtml.render(Mode::Xhtml);
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let result = {
+                #[allow(unused_imports)]
+                use ::rdxl::interpolate::{IoTarget as _, FmtTarget as _};
+                let mut stream = (&::rdxl::interpolate::Target::new(#writer)).stream();
+                #xhtmls
+                stream.finish()
+            };
+            result
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xmarkup!](https://andrew-johnson-4.github.io/rdxl) macro renders xhtml as trusted Markup
+///
+/// <b>xmarkup!</b> accepts the same input as <b>xhtml!</b> but returns a <b>Markup</b> value instead of a String.
+/// Markup is not escaped again when it is interpolated into another template, so it is the right choice
+/// for helper functions that return fragments of a page.
+///
+/// ```
+/// # use rdxl::{xhtml,xmarkup,Markup};
+/// fn item(name: &str) -> Markup {
+///    xmarkup!(<li>{{ name }}</li>)
+/// }
+///
+/// assert_eq!(
+///    xhtml!(<ul>{{ item("a&b") }}</ul>),
+///    "<ul><li>a&amp;b</li></ul>"
+/// );
+/// ```
+#[proc_macro]
+pub fn xmarkup(input: TokenStream) -> TokenStream {
+    let xhtmls = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Xhtml);
+    let capacity = xhtmls.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xhtmls
+                stream
+            };
+            ::rdxl::PreEscaped(stream)
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xtext!](https://andrew-johnson-4.github.io/rdxl) macro is the primary mechanism for templating in rdxl
+///
+/// <b>xtext!</b> consumes mixed Rust code and XML markup as input and emits rendered xhtml to a string buffer.
+/// Rust code is usually delimited by {{double braces}} or [[double brackets]]. The <b>syn</b> module is used to
+/// allow most Rust expressions to be used inside the correct delimited contexts. Control flow structures
+/// such as if/else blocks, loops, and let statements may be used inline as well.
+///
+/// Aside from standard XML syntax, custom types may be defined with <b>xtext!</b> and <b>xrender!</b> facilities. This
+/// encourages typesafe modular templates to be created and shared.
+///
+/// Use of <b>xtext!</b> usually looks something like this:
+/// ```
+/// # use rdxl::xtext;
+/// # fn main() {
+/// let mut x = 5;
+///
+/// println!("{}",xtext!(<div>
+///    {{ x }},
+///    {{ x = 3; }}
+///    {{ x }},
+///    {{ x = 7; }}
+///    {{ x }},
+///    {{ let mut y = 2 }}
+///    {{ y }},
+///    {{ y = 1; }}
+///    {{ y }}
+///    {{ for i in (0..x) {{
+///       <span>{{i}}</span>
+///    }} }}
+/// </div>));
+/// # }
+/// ```
+#[proc_macro]
+pub fn xtext(input: TokenStream) -> TokenStream {
+    let xtexts = parse_macro_input!(input as xhtml::Xhtml).render(Mode::Xtext);
+    let capacity = xtexts.capacity();
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let stream = {
+                let mut stream = String::with_capacity(#capacity);
+                #xtexts
+                stream
+            };
+            stream
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xtext_to!](https://andrew-johnson-4.github.io/rdxl) macro renders xtext directly into a writer
+///
+/// <b>xtext_to!</b> is to <b>xtext!</b> as <b>xhtml_to!</b> is to <b>xhtml!</b>.
+///
+/// ```
+/// # use rdxl::xtext_to;
+/// # fn main() -> std::fmt::Result {
+/// let mut s = String::new();
+/// xtext_to!(&mut s, <p>{{ "a" }} {{ "b" }}</p>)?;
+/// assert_eq!(s, "<p>ab</p>");
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro]
+pub fn xtext_to(input: TokenStream) -> TokenStream {
+    let xtext_to = parse_macro_input!(input as xhtml_to::XtextTo);
+
+    let writer = xtext_to.writer;
+    let xtexts = xtext_to.xtext.render(Mode::Xtext);
+
+    let expanded = quote! {
+        {
+            #[allow(clippy::all)]
+            let result = {
+                #[allow(unused_imports)]
+                use ::rdxl::interpolate::{IoTarget as _, FmtTarget as _};
+                let mut stream = (&::rdxl::interpolate::Target::new(#writer)).stream();
+                #xtexts
+                stream.finish()
+            };
+            result
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xtype!](https://andrew-johnson-4.github.io/rdxl) macro defines an xml element and subelements
+///
+/// <b>xtype!</b> removes some of the redundancy of defining types having many attribute fields
+/// and many heterogenous children elements. A typical type definition that is a good fit for
+/// this macro would be *coincidentally* most XML elements.
+///
+/// In <b>xtype!</b>, a definition might look like this:
+/// ```
+/// # use rdxl::xtype;
+/// # pub struct MyPredefinedType {}
+/// xtype!(<!MyList my_string:String my_int:u64>
+///   <!MyItem my_bool:bool/>
+///   <!MyOtherItem my_char:char/>
+///   <?MyPredefinedType/>
+/// </MyList>);
+/// # fn main() {}
+/// ```
+///
+/// In sugar-free Rust this would become like this:
+/// ```
+/// struct MyPredefinedType {}
+/// struct MyItem { my_bool: bool }
+/// struct MyOtherItem { my_char: char }
+/// enum MyListChildren {
+///    MyItem(MyItem),
+///    MyOtherItem(MyOtherItem),
+///    MyPredefinedType(MyPredefinedType)
+/// }
+/// struct MyList {
+///    my_string: String,
+///    my_int: u64,
+///    children: Vec<MyListChildren>
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Each type also gets a builder, which <b>xhtml!</b> uses to construct elements. An attribute
+/// marked with `!` is required. It has no default value, so its type need not implement Default,
+/// and constructing the element without it is a compile error.
+/// ```compile_fail
+/// # use rdxl::{xtype,xrender,xhtml};
+/// xtype!(<!Card title!:String/>);
+/// xrender!(Card, <h1>{{ self.title }}</h1>);
+/// # fn main() {
+/// xhtml!(<!Card/>);
+/// # }
+/// ```
+#[proc_macro]
+pub fn xtype(input: TokenStream) -> TokenStream {
+    let xtype = parse_macro_input!(input as xtype::XType);
+
+    let expanded = quote! {
+       #xtype
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xrender!](https://andrew-johnson-4.github.io/rdxl) macro defines a Display implementation for a type
+///
+/// <b>xrender!</b> implements the <b>Display</b> property for XHtml-like types.
+/// The type definition is separate from the display logic for in the case that
+/// a separate backend is desired
+///
+/// A typical invocation would look like this:
+/// ```
+/// # use rdxl::{xtype,xrender};
+/// # pub struct MyPredefinedType {}
+/// # xtype!(<!MyList my_string:String my_int:u64>
+/// #   <!MyItem my_bool:bool/>
+/// #   <!MyOtherItem my_char:char/>
+/// #   <?MyPredefinedType/>
+/// # </MyList>);
+/// xrender!(MyList, <ul>
+///   <li>{{ self.my_string }}</li>
+///   <li>{{ self.my_int }}</li>
+///   {{ for i in self.children.iter() {{
+///     {{ if let MyListChildren::MyItem(my_item) = i {{
+///       <li>MyItem: {{ my_item.my_bool }}</li>
+///     }} else if let MyListChildren::MyOtherItem(my_other_item) = i {{
+///       <li>MyOtherItem: {{ my_other_item.my_char }}</li>
+///     }} }}
+///   }} }}
+/// </ul>);
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn xrender(input: TokenStream) -> TokenStream {
+    let xrender = parse_macro_input!(input as xrender::XRender);
+
+    let xname = xrender.name;
+    let (impl_generics, ty_generics, where_clause) = xrender.generics.split_for_impl();
+
+    if let Some(layout) = xrender.layout {
+       return xrender_page(&xrender.xhtml, layout, quote! { #impl_generics }, quote! { #xname #ty_generics }, quote! { #where_clause });
+    }
+
+    let xxhtml = xrender.xhtml.render(Mode::Xhtml);
+
+    let expanded = quote! {
+       #[allow(clippy::all)]
+       impl #impl_generics std::fmt::Display for #xname #ty_generics #where_clause {
+          fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+              let mut stream = ::rdxl::stream::FmtStream::new(f);
+              #xxhtml
+              stream.finish()
+          }
+       }
+       impl #impl_generics ::rdxl::RawDisplay for #xname #ty_generics #where_clause {}
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Implements a layout for a page whose markup overrides some of its blocks
+fn xrender_page(xhtml: &xhtml::Xhtml, layout: syn::Path, impl_generics: proc_macro2::TokenStream,
+                ty: proc_macro2::TokenStream, where_clause: proc_macro2::TokenStream) -> TokenStream {
+    let mut names: Vec<&proc_macro2::Ident> = Vec::new();
+    let mut blocks = Vec::new();
+    for c in xhtml.crumbs.iter() {
+       let block = match c {
+          xhtml::XhtmlCrumb::E(x) => { x.block() },
+          _ => { None }
+       };
+       let (name, cs) = match block {
+          Some(b) => { b },
+          None => {
+             let msg = "Expected a block in an xrender! that extends a layout";
+             return TokenStream::from(syn::Error::new(c.span(), msg).to_compile_error());
+          }
+       };
+       if names.contains(&name) {
+          let msg = format!("block {} is already defined", name);
+          return TokenStream::from(syn::Error::new(name.span(), msg).to_compile_error());
+       }
+       names.push(name);
+
+       let mut e = Emitter::new(Mode::Xhtml);
+       for c in cs.iter() { c.emit(&mut e); }
+       blocks.push(quote! {
+          #[allow(unused_variables, unused_mut)]
+          fn #name<W: std::fmt::Write>(&self, mut stream: &mut ::rdxl::stream::FmtStream<W>) {
+             #e
+          }
+       });
+    }
+
+    let expanded = quote! {
+       #[allow(clippy::all)]
+       impl #impl_generics #layout for #ty #where_clause {
+          #(#blocks)*
+       }
+       #[allow(clippy::all)]
+       impl #impl_generics std::fmt::Display for #ty #where_clause {
+          fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+              let mut stream = ::rdxl::stream::FmtStream::new(f);
+              <Self as #layout>::layout(self, &mut stream);
+              stream.finish()
+          }
+       }
+       impl #impl_generics ::rdxl::RawDisplay for #ty #where_clause {}
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xlayout!](https://andrew-johnson-4.github.io/rdxl) macro defines a layout that pages can extend
+///
+/// <b>xlayout!</b> defines a trait with the given name. The markup of the layout may contain blocks,
+/// written {{ block name {{ default markup }} }}, which become methods of the trait.
+/// A type extends the layout with <b>xrender!</b> by naming the layout after the type. The markup
+/// of the page is then a list of blocks that replace the defaults, and the rest of the layout
+/// is rendered as it was written.
+///
+/// ```
+/// # use rdxl::{xlayout,xrender};
+/// xlayout!(Base, <html>
+///   <head><title>{{ block title {{ Untitled }} }}</title></head>
+///   <body>{{ block body {{ }} }}</body>
+/// </html>);
+///
+/// struct Home { user: String }
+/// xrender!(Home: Base,
+///   {{ block body {{ <p>Welcome, {{ self.user }}</p> }} }}
+/// );
+/// # fn main() {
+/// assert_eq!(Home { user: "Ann".to_string() }.to_string(),
+///    "<html> <head><title>Untitled</title></head> <body><p>Welcome, Ann</p></body> </html>");
+/// # }
+/// ```
+#[proc_macro]
+pub fn xlayout(input: TokenStream) -> TokenStream {
+    let xlayout = parse_macro_input!(input as xlayout::XLayout);
+
+    let xname = xlayout.name;
+    let mut e = Emitter::layout(Mode::Xhtml, xname.clone());
+    xlayout.xhtml.emit(&mut e);
+    let blocks = e.blocks().iter().map(|(name, body)| quote! {
+       #[allow(unused_variables, unused_mut)]
+       fn #name<W: std::fmt::Write>(&self, mut stream: &mut ::rdxl::stream::FmtStream<W>) {
+          #body
+       }
+    });
+
+    let expanded = quote! {
+       #[allow(clippy::all)]
+       pub trait #xname {
+          #(#blocks)*
+          /// Renders the layout with the blocks of this type
+          #[allow(unused_mut)]
+          fn layout<W: std::fmt::Write>(&self, mut stream: &mut ::rdxl::stream::FmtStream<W>) {
+             #e
+          }
+       }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The [xtextrender!](https://andrew-johnson-4.github.io/rdxl) macro defines a Display implementation for a type
+///
+/// <b>xtextrender!</b> implements the <b>Display</b> property for XHtml-like types.
+/// The type definition is separate from the display logic for in the case that
+/// a separate backend is desired
+///
+/// A typical invocation would look like this:
+/// ```
+/// # use rdxl::{xtype,xtextrender};
+/// # pub struct MyPredefinedType {}
+/// # xtype!(<!MyList my_string:String my_int:u64>
+/// #   <!MyItem my_bool:bool/>
+/// #   <!MyOtherItem my_char:char/>
+/// #   <?MyPredefinedType/>
+/// # </MyList>);
+/// xtextrender!(MyList, <ul>
+///   <li>{{ self.my_string }}</li>
+///   <li>{{ self.my_int }}</li>
+///   {{ for i in self.children.iter() {{
+///     {{ if let MyListChildren::MyItem(my_item) = i {{
+///       <li>MyItem: {{ my_item.my_bool }}</li>
+///     }} else if let MyListChildren::MyOtherItem(my_other_item) = i {{
+///       <li>MyOtherItem: {{ my_other_item.my_char }}</li>
+///     }} }}
+///   }} }}
+/// </ul>);
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn xtextrender(input: TokenStream) -> TokenStream {
+    let xtextrender = parse_macro_input!(input as xtextrender::XtextRender);
+
+    let xname = xtextrender.name;
+    let (impl_generics, ty_generics, where_clause) = xtextrender.generics.split_for_impl();
+    let xxtext = xtextrender.xtext.render(Mode::Xtext);
+
+    let expanded = quote! {
+       #[allow(clippy::all)]
+       impl #impl_generics std::fmt::Display for #xname #ty_generics #where_clause {
+          fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+              let mut stream = ::rdxl::stream::FmtStream::new(f);
+              #xxtext
+              stream.finish()
+          }
+       }
+       impl #impl_generics ::rdxl::RawDisplay for #xname #ty_generics #where_clause {}
+    };
+
+    TokenStream::from(expanded)
+}
+
@@ -0,0 +1,118 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::str::FromStr;
+use proc_macro2::{Span, TokenStream, TokenTree, Group};
+use quote::{quote};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{LitStr};
+use rdxl_internals::core::Mode;
+use crate::xhtml::{Xhtml, XhtmlStr};
+
+/// A template written as real HTML in a string literal
+pub struct XhtmlStrLit {
+   pub html: LitStr
+}
+impl Parse for XhtmlStrLit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(XhtmlStrLit {
+            html: input.parse()?
+        })
+    }
+}
+
+/// Gives every token the span that `span` returns for it, visiting a token and then the tokens inside of it
+fn respan(ts: TokenStream, span: &mut dyn FnMut() -> Span) -> TokenStream {
+    ts.into_iter().map(|t| {
+       let at = span();
+       match t {
+          TokenTree::Group(g) => {
+             let mut group = Group::new(g.delimiter(), respan(g.stream(), span));
+             group.set_span(at);
+             TokenTree::Group(group)
+          },
+          mut t => { t.set_span(at); t }
+       }
+    }).collect()
+}
+
+impl XhtmlStrLit {
+    /// The span of a template range, when the compiler can split the literal, which is a nightly feature
+    fn subspan(&self, (start, end): (usize, usize)) -> Option<Span> {
+        //the text of a raw string is written as is, as is a plain string without escapes
+        let html = self.html.token();
+        let repr = html.to_string();
+        let quote = repr.find('"').filter(|_| repr.starts_with('r') || !repr.contains('\\'))?;
+        if start == end { return None; }
+        html.subspan(quote+1+start .. quote+1+end)
+    }
+
+    /// The index of the token that a parse error is at
+    ///
+    /// On stable every token has the span of the whole literal, so the tokens are parsed again with
+    /// the span of the macro call given to the first few of them until the error moves onto that span.
+    fn error_at(&self, tokens: &TokenStream, count: usize) -> Option<usize> {
+        let call_site = Span::call_site().source_text()?;
+        let moved = |k: usize| {
+            let mut i = 0;
+            let tokens = respan(tokens.clone(), &mut || {
+               i += 1;
+               if i <= k { Span::call_site() } else { self.html.span() }
+            });
+            syn::parse2::<Xhtml>(tokens).err()
+               .and_then(|e| e.span().source_text())
+               .map(|text| text == call_site).unwrap_or(false)
+        };
+        if moved(0) || !moved(count) { return None; }
+        let (mut low, mut high) = (0, count);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if moved(mid) { high = mid; } else { low = mid; }
+        }
+        Some(low)
+    }
+
+    /// Renders the template verbatim, except for its Rust islands
+    ///
+    /// The template is rendered in xtext mode, so its whitespace is kept as it was written
+    /// rather than being collapsed into breaking spaces. The tokens of the islands are lexed by
+    /// the compiler and located in the template by their text, so that errors in them point into it.
+    pub fn expand(&self) -> Result<TokenStream> {
+        let html = self.html.value();
+        let located = |offset: usize, msg: &dyn std::fmt::Display| {
+            let (line, column) = XhtmlStr::line_column(&html, offset);
+            Error::new(self.html.span(), format!("{}:{}: {}", line, column, msg))
+        };
+        let xstr = XhtmlStr::lex(&html).map_err(|e| located(e.offset, &e.message))?;
+        let tokens = TokenStream::from_str(&xstr.source)
+            .map_err(|e| Error::new(self.html.span(), e))?;
+        let ranges = xstr.locate(tokens.clone()).unwrap_or_default();
+
+        let mut at = ranges.iter();
+        let tokens = respan(tokens, &mut || {
+            at.next().and_then(|&range| self.subspan(range)).unwrap_or(self.html.span())
+        });
+        let xhtml = syn::parse2::<Xhtml>(tokens.clone()).map_err(|e| {
+            match self.error_at(&tokens, ranges.len()) {
+               Some(i) => { located(ranges[i].0, &e) },
+               None => { Error::new(e.span(), e) },
+            }
+        })?;
+        let xtexts = xhtml.render(Mode::Xtext);
+        let capacity = xtexts.capacity();
+
+        Ok(quote! {
+            {
+                #[allow(clippy::all)]
+                let stream = {
+                    let mut stream = String::with_capacity(#capacity);
+                    #xtexts
+                    stream
+                };
+                stream
+            }
+        })
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Expr, Token};
+use crate::xhtml::Xhtml;
+
+#[allow(dead_code)]
+pub struct XhtmlTo {
+   pub writer: Expr,
+   pub comma: Token![,],
+   pub xhtml: Xhtml
+}
+impl Parse for XhtmlTo {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(XhtmlTo {
+            writer: input.parse()?,
+            comma: input.parse()?,
+            xhtml: input.parse()?
+        })
+    }
+}
+
+#[allow(dead_code)]
+pub struct XtextTo {
+   pub writer: Expr,
+   pub comma: Token![,],
+   pub xtext: Xhtml
+}
+impl Parse for XtextTo {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(XtextTo {
+            writer: input.parse()?,
+            comma: input.parse()?,
+            xtext: input.parse()?
+        })
+    }
+}
@@ -5,17 +5,18 @@
 
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::Token;
+use syn::{Token};
 use crate::xhtml::Xhtml;
 
-pub struct XRender {
+#[allow(dead_code)]
+pub struct XLayout {
    pub name: Ident,
    pub comma: Token![,],
    pub xhtml: Xhtml
 }
-impl Parse for XRender {
+impl Parse for XLayout {
     fn parse(input: ParseStream) -> Result<Self> {
-        Ok(XRender {
+        Ok(XLayout {
             name: input.parse()?,
             comma: input.parse()?,
             xhtml: input.parse()?
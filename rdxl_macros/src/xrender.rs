@@ -0,0 +1,32 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::Ident;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token, Generics, Path};
+use crate::xhtml::Xhtml;
+
+#[allow(dead_code)]
+pub struct XRender {
+   pub name: Ident,
+   pub generics: Generics,
+   pub layout: Option<Path>,
+   pub comma: Token![,],
+   pub xhtml: Xhtml
+}
+impl Parse for XRender {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(XRender {
+            name: input.parse()?,
+            generics: input.parse()?,
+            layout: if input.peek(Token![:]) {
+               let _colon: Token![:] = input.parse()?;
+               Some(input.parse()?)
+            } else { None },
+            comma: input.parse()?,
+            xhtml: input.parse()?
+        })
+    }
+}
@@ -5,18 +5,21 @@
 
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::Token;
-use crate::xtext::Xtext;
+use syn::{Token, Generics};
+use crate::xhtml::Xhtml;
 
+#[allow(dead_code)]
 pub struct XtextRender {
    pub name: Ident,
+   pub generics: Generics,
    pub comma: Token![,],
-   pub xtext: Xtext
+   pub xtext: Xhtml
 }
 impl Parse for XtextRender {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(XtextRender {
             name: input.parse()?,
+            generics: input.parse()?,
             comma: input.parse()?,
             xtext: input.parse()?
         })
@@ -0,0 +1,463 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{format_ident, quote_spanned,TokenStreamExt, ToTokens};
+use proc_macro2::{Group, Delimiter};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{braced,parse_quote,Ident,Type,Token,Attribute,Expr,Generics,GenericParam,token};
+use syn::spanned::Spanned;
+
+#[allow(dead_code)]
+pub struct XTypeAttrDefault {
+   pub eq: Token![=],
+   pub brace1: token::Brace,
+   pub brace2: token::Brace,
+   pub expr: Expr
+}
+impl Parse for XTypeAttrDefault {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content1;
+        let content2;
+        Ok(XTypeAttrDefault {
+            eq: input.parse()?,
+            brace1: braced!(content1 in input),
+            brace2: braced!(content2 in content1),
+            expr: content2.parse()?
+        })
+    }
+}
+
+#[allow(dead_code)]
+pub struct XTypeAttr {
+   pub attr_name: Ident,
+   pub required: Option<Token![!]>,
+   pub eq: Token![:],
+   pub attr_type: Type,
+   pub attr_expr: Option<XTypeAttrDefault>
+}
+impl Parse for XTypeAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attr = XTypeAttr {
+            attr_name: input.parse()?,
+            required: input.parse()?,
+            eq: input.parse()?,
+            attr_type: input.parse()?,
+            attr_expr: (if input.peek(Token![=]) {Some(input.parse()?)} else {None})
+        };
+        if let (Some(r), Some(_)) = (&attr.required, &attr.attr_expr) {
+            return Err(Error::new(r.span, "A required attribute cannot have a default value"));
+        }
+        Ok(attr)
+    }
+}
+
+pub struct XType {
+   pub comms_outer: Vec<Attribute>,
+   pub comms_inner: Vec<Attribute>,
+   pub open: Token![<],
+   pub defined: bool,
+   pub tag_name: String,
+   pub generics: Generics,
+   pub tag_attrs: Vec<XTypeAttr>,
+   pub tag_children: Vec<XType>,
+   pub slots: Vec<Ident>,
+   pub close: Token![>],
+}
+
+impl XType {
+    fn camel_case(s: &str) -> String {
+       s.split('_').map(|w| {
+          let mut cs = w.chars();
+          match cs.next() {
+             Some(c) => { c.to_uppercase().chain(cs).collect() }
+             None => { String::new() }
+          }
+       }).collect()
+    }
+    fn param_name(p: &GenericParam) -> String {
+       match p {
+          GenericParam::Lifetime(l) => { l.lifetime.to_string() }
+          GenericParam::Type(t) => { t.ident.to_string() }
+          GenericParam::Const(c) => { c.ident.to_string() }
+       }
+    }
+    /// The parameters of the children enum, which are those used by the children
+    fn children_generics(&self) -> Generics {
+       let mut used = Vec::new();
+       for child in self.tag_children.iter() {
+          if child.tag_name == "Display" {
+             if let Some(l) = self.generics.lifetimes().next() {
+                used.push(l.lifetime.to_string());
+             }
+          }
+          for p in child.generics.params.iter() {
+             used.push(XType::param_name(p));
+          }
+       }
+       let mut generics = self.generics.clone();
+       generics.params = self.generics.params.iter()
+          .filter(|p| used.contains(&XType::param_name(p)))
+          .cloned().collect();
+       generics
+    }
+}
+
+impl ToTokens for XType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+       if self.defined { return; }
+
+       for ci in self.comms_outer.iter() {
+          ci.to_tokens(tokens);
+       }
+       for ci in self.comms_inner.iter() {
+          ci.to_tokens(tokens);
+       }
+
+       let span = self.open.span.join(self.close.span).unwrap_or(self.open.span);
+
+       let tag_name = format_ident!("{}", self.tag_name, span=span);
+       let generics = &self.generics;
+       let (impl_generics, ty_generics, _) = generics.split_for_impl();
+       (quote_spanned! {span=>
+          pub struct #tag_name #generics
+       }).to_tokens(tokens);
+
+       let mut ts = proc_macro2::TokenStream::new();
+       for XTypeAttr { attr_name, attr_type, .. } in self.tag_attrs.iter() {
+          let span = attr_name.span().join(attr_type.span()).unwrap_or(attr_name.span());
+          (quote_spanned! {span=>
+             pub #attr_name : #attr_type,
+          }).to_tokens(&mut ts);
+       }
+
+       //each named slot is a separate field of rendered markup
+       for slot in self.slots.iter() {
+          (quote_spanned! {slot.span()=>
+             pub #slot : ::rdxl::Markup,
+          }).to_tokens(&mut ts);
+       }
+
+       let child_type = format_ident!("{}Children", self.tag_name, span=span);
+       let child_generics = self.children_generics();
+       let (_, child_ty_generics, _) = child_generics.split_for_impl();
+       (quote_spanned! {span=>
+          pub children : Vec<#child_type #child_ty_generics>,
+       }).to_tokens(&mut ts);
+
+       (quote_spanned! {span=> {#ts}}).to_tokens(tokens);
+
+       (quote_spanned! {span=>
+          pub enum #child_type #child_generics
+       }).to_tokens(tokens);
+
+       let mut ts = proc_macro2::TokenStream::new();
+       for child in self.tag_children.iter() {
+          if child.tag_name == "Display" {
+             //borrowed content may be displayed if the type has a lifetime
+             match child_generics.lifetimes().next() {
+                Some(l) => {
+                   let l = &l.lifetime;
+                   (quote_spanned! {span=> Display(Box<dyn ::rdxl::RawDisplay + #l>),}).to_tokens(&mut ts);
+                }
+                None => {
+                   (quote_spanned! {span=> Display(Box<dyn ::rdxl::RawDisplay>),}).to_tokens(&mut ts);
+                }
+             }
+          } else {
+             let child_tag = format_ident!("{}", child.tag_name, span=span);
+             let (_, child_generics, _) = child.generics.split_for_impl();
+             (quote_spanned! {span=> #child_tag(#child_tag #child_generics),}).to_tokens(&mut ts);
+          }
+       }
+
+       let gr = Group::new(Delimiter::Brace, ts);
+       tokens.append(gr);
+
+       //required attributes are type parameters of the builder, which start as Missing
+       let builder = format_ident!("{}Builder", self.tag_name, span=span);
+       let required: Vec<(&XTypeAttr,Ident)> = self.tag_attrs.iter()
+          .filter(|a| a.required.is_some())
+          .map(|a| (a, format_ident!("{}Attr", XType::camel_case(&a.attr_name.to_string()), span=a.attr_name.span())))
+          .collect();
+       let type_args: Vec<proc_macro2::TokenStream> = generics.params.iter().map(|p| match p {
+          GenericParam::Lifetime(l) => { l.lifetime.to_token_stream() }
+          GenericParam::Type(t) => { t.ident.to_token_stream() }
+          GenericParam::Const(c) => { c.ident.to_token_stream() }
+       }).collect();
+       let builder_type = |given: Option<&Ident>| {
+          let rs = required.iter().map(|(a,r)| {
+             if given == Some(r) { a.attr_type.to_token_stream() } else { r.to_token_stream() }
+          });
+          quote_spanned! {span=> #builder<#(#type_args,)* #(#rs),*> }
+       };
+       let missing = required.iter().map(|_| quote_spanned! {span=> ::rdxl::Missing });
+       let missing_type = quote_spanned! {span=> #builder<#(#type_args,)* #(#missing),*> };
+
+       let mut builder_generics = generics.clone();
+       for (_,r) in required.iter() {
+          builder_generics.params.push(parse_quote! { #r });
+       }
+       let (builder_impl_generics, _, _) = builder_generics.split_for_impl();
+       let given = required.iter().map(|(a,r)| {
+          let t = &a.attr_type;
+          quote_spanned! {a.attr_name.span()=> #r: ::rdxl::Given<#t> }
+       });
+
+       let mut fs = proc_macro2::TokenStream::new();
+       let mut ds = proc_macro2::TokenStream::new();
+       let mut ss = proc_macro2::TokenStream::new();
+       let mut bs = proc_macro2::TokenStream::new();
+       for a in self.tag_attrs.iter() {
+          let XTypeAttr { attr_name, attr_type, attr_expr, .. } = a;
+          let span = attr_name.span().join(attr_type.span()).unwrap_or(attr_name.span());
+
+          if let Some((_,r)) = required.iter().find(|(ra,_)| ra.attr_name == *attr_name) {
+             (quote_spanned! {span=> #attr_name: #r, }).to_tokens(&mut fs);
+             (quote_spanned! {span=> #attr_name: ::rdxl::Missing, }).to_tokens(&mut ds);
+             (quote_spanned! {span=> #attr_name: self.#attr_name.given(), }).to_tokens(&mut bs);
+
+             let given = builder_type(Some(r));
+             let rest = self.tag_attrs.iter().map(|o| &o.attr_name).filter(|o| *o != attr_name)
+                .chain(self.slots.iter()).map(|o| {
+                quote_spanned! {span=> #o: self.#o, }
+             });
+             (quote_spanned! {span=>
+                pub fn #attr_name(self, v: #attr_type) -> #given {
+                   #builder {
+                      #attr_name: v,
+                      #(#rest)*
+                      children: self.children,
+                   }
+                }
+             }).to_tokens(&mut ss);
+          } else {
+             (quote_spanned! {span=> #attr_name: #attr_type, }).to_tokens(&mut fs);
+             if let Some(ae) = attr_expr {
+                let e = &ae.expr;
+                (quote_spanned! {span=> #attr_name: #e, }).to_tokens(&mut ds);
+             } else {
+                (quote_spanned! {span=> #attr_name: std::default::Default::default(), }).to_tokens(&mut ds);
+             }
+             (quote_spanned! {span=> #attr_name: self.#attr_name, }).to_tokens(&mut bs);
+             (quote_spanned! {span=>
+                pub fn #attr_name(mut self, v: #attr_type) -> Self {
+                   self.#attr_name = v;
+                   self
+                }
+             }).to_tokens(&mut ss);
+          }
+       }
+
+       for slot in self.slots.iter() {
+          (quote_spanned! {slot.span()=> #slot: ::rdxl::Markup, }).to_tokens(&mut fs);
+          (quote_spanned! {slot.span()=> #slot: std::default::Default::default(), }).to_tokens(&mut ds);
+          (quote_spanned! {slot.span()=> #slot: self.#slot, }).to_tokens(&mut bs);
+          (quote_spanned! {slot.span()=>
+             pub fn #slot(mut self, v: ::rdxl::Markup) -> Self {
+                self.#slot = v;
+                self
+             }
+          }).to_tokens(&mut ss);
+       }
+
+       //defaulted attributes of generic types are defaulted only when their type is
+       let mut default_generics = generics.clone();
+       if !generics.params.is_empty() {
+          let wc = default_generics.make_where_clause();
+          for XTypeAttr { attr_type, attr_expr, required, .. } in self.tag_attrs.iter() {
+             if attr_expr.is_none() && required.is_none() {
+                wc.predicates.push(parse_quote! { #attr_type: std::default::Default });
+             }
+          }
+       }
+       let (_, _, where_clause) = default_generics.split_for_impl();
+       let builder_type_self = builder_type(None);
+       let builder_doc = format!("A builder for [{0}]({0}), which can only build once every required attribute is given", self.tag_name);
+
+       (quote_spanned! {span=>
+          #[doc = #builder_doc]
+          pub struct #builder #builder_generics {
+             #fs
+             children: Vec<#child_type #child_ty_generics>,
+          }
+
+          #[allow(clippy::all)]
+          impl #impl_generics #tag_name #ty_generics #where_clause {
+             pub fn builder() -> #missing_type {
+                #builder {
+                   #ds
+                   children: Vec::new(),
+                }
+             }
+          }
+
+          #[allow(clippy::all)]
+          impl #builder_impl_generics #builder_type_self {
+             #ss
+             pub fn children(mut self, v: Vec<#child_type #child_ty_generics>) -> Self {
+                self.children = v;
+                self
+             }
+             pub fn build(self) -> #tag_name #ty_generics where #(#given),* {
+                #[allow(unused_imports)]
+                use ::rdxl::Given as _;
+                #tag_name {
+                   #bs
+                   children: self.children,
+                }
+             }
+          }
+       }).to_tokens(tokens);
+
+       //types with required attributes have no default value
+       if required.is_empty() {
+          (quote_spanned! {span=>
+             #[allow(clippy::all)]
+             impl #impl_generics std::default::Default for #tag_name #ty_generics #where_clause {
+                fn default() -> Self {
+                   #tag_name::builder().build()
+                }
+             }
+          }).to_tokens(tokens);
+       }
+
+       for child in self.tag_children.iter() {
+          child.to_tokens(tokens);
+       }
+    }
+}
+
+impl XType {
+    /// Parses a named slot like <slot:header/>
+    fn parse_slot(input: ParseStream) -> Result<Ident> {
+        let _open: Token![<] = input.parse()?;
+        let kw: Ident = input.parse()?;
+        if kw != "slot" {
+           return Err(Error::new(kw.span(), "Expected a child type or a <slot:name/>"));
+        }
+        let _colon: Token![:] = input.parse()?;
+        let name: Ident = input.parse()?;
+        let _slash: Token![/] = input.parse()?;
+        let _close: Token![>] = input.parse()?;
+        Ok(name)
+    }
+}
+
+impl Parse for XType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let comms_outer: Vec<Attribute>  = input.call(Attribute::parse_outer)?;
+        let comms_inner: Vec<Attribute>  = input.call(Attribute::parse_inner)?;
+        let open: Token![<] = input.parse()?;
+
+        if input.peek(Token![?]) && input.peek2(Token![/]) {
+           let _q: Token![?] = input.parse()?;
+           let _s: Token![/] = input.parse()?;
+           let close: Token![>] = input.parse()?;
+
+           return Ok(XType {
+              comms_outer: comms_outer,
+              comms_inner: comms_inner,
+              open: open,
+              defined: true,
+              tag_name: "Display".to_string(),
+              generics: Generics::default(),
+              tag_attrs: Vec::new(),
+              tag_children: Vec::new(),
+              slots: Vec::new(),
+              close: close
+           })
+        } else if input.peek(Token![?]) {
+           let _q: Token![?] = input.parse()?;
+           let tag_name: Ident = input.parse()?;
+           let tag_name = tag_name.to_string();
+           let generics: Generics = input.parse()?;
+           let _s: Token![/] = input.parse()?;
+           let close: Token![>] = input.parse()?;
+
+           return Ok(XType {
+              comms_outer: comms_outer,
+              comms_inner: comms_inner,
+              open: open,
+              defined: true,
+              tag_name: tag_name,
+              generics: generics,
+              tag_attrs: Vec::new(),
+              tag_children: Vec::new(),
+              slots: Vec::new(),
+              close: close
+           })
+        }
+
+        let _exc: Token![!] = input.parse()?;
+        let tag_name: Ident = input.parse()?;
+        let tag_name = tag_name.to_string();
+        let generics: Generics = input.parse()?;
+
+        let mut tag_attrs = Vec::new();
+        while input.peek(Ident) {
+           let attr: XTypeAttr = input.parse()?;
+           tag_attrs.push(attr);
+        }
+
+        if input.peek(Token![/]) {
+           let _backslash: Token![/] = input.parse()?;
+           let close: Token![>] = input.parse()?;
+           Ok(XType {
+              comms_outer: comms_outer,
+              comms_inner: comms_inner,
+              open: open,
+              defined: false,
+              tag_name: tag_name,
+              generics: generics,
+              tag_attrs: tag_attrs,
+              tag_children: Vec::new(),
+              slots: Vec::new(),
+              close: close
+           })
+        } else {
+           let _close_opening_tag: Token![>] = input.parse()?;
+
+           let mut children = Vec::new();
+           let mut slots: Vec<Ident> = Vec::new();
+           while input.peek(Token![<]) && (input.peek2(Token![!]) || input.peek2(Token![?]) || input.peek2(Ident)) {
+              if input.peek2(Ident) {
+                 let slot = XType::parse_slot(input)?;
+                 if tag_attrs.iter().any(|a| a.attr_name == slot) || slots.contains(&slot) || slot == "children" {
+                    let msg = format!("The slot {} has the same name as another field of {}", slot, tag_name);
+                    return Err(Error::new(slot.span(), msg));
+                 }
+                 slots.push(slot);
+                 continue;
+              }
+              let child: XType = input.parse()?;
+              children.push(child);
+           }
+
+           let _open_close: Token![<] = input.parse()?;
+           let _open_close2: Token![/] = input.parse()?;
+           let close_ident: Ident = input.parse()?;
+
+           if tag_name.to_string() != close_ident.to_string() {
+              let msg = format!("Expected </{}> found </{}>", tag_name, close_ident);
+              let r = Error::new(close_ident.span(), msg);
+              return Err(r)
+           }
+
+           let close: Token![>] = input.parse()?;
+           Ok(XType {
+              comms_outer: comms_outer,
+              comms_inner: comms_inner,
+              open: open,
+              defined: false,
+              tag_name: tag_name,
+              generics: generics,
+              tag_attrs: tag_attrs,
+              tag_children: children,
+              slots: slots,
+              close: close
+           })
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+/// The value of a required attribute that has not been given to a builder yet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Missing;
+
+/// A required attribute value of type `T`, implemented for `T` but not for Missing
+///
+/// Builders generated by <b>xtype!</b> can only build an element when every required
+/// attribute is Given, so omitting one is a compile error.
+#[diagnostic::on_unimplemented(
+   message = "missing a required attribute of type `{T}`",
+   label = "this element is missing a required attribute",
+)]
+pub trait Given<T> {
+   fn given(self) -> T;
+}
+impl<T> Given<T> for T {
+   fn given(self) -> T {
+      self
+   }
+}
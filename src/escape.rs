@@ -0,0 +1,206 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Escaping of interpolated values
+//!
+//! Values interpolated into markup with {{double braces}} are escaped by default.
+//! In text position the characters `&`, `<` and `>` are replaced with entities.
+//! In attribute position the double quote `"` is replaced as well, so that the
+//! value cannot terminate the quoted attribute early.
+//! Values of a structured style attribute are also escaped as CSS, so that they
+//! cannot end their declaration or the rule.
+//!
+//! The text of a comment with interpolated values is escaped so that it cannot end
+//! the comment early.
+//!
+//! The <b>xml!</b> macro escapes with the stricter XML rules instead: characters that
+//! XML does not allow in a document are replaced, and attribute values keep their
+//! tabs and newlines as character references so that attribute normalization
+//! does not turn them into spaces.
+
+use std::fmt::{self, Write};
+
+fn escape<W: Write + ?Sized>(w: &mut W, s: &str, quote: bool) -> fmt::Result {
+   let mut last = 0;
+   for (i, c) in s.char_indices() {
+      let entity = match c {
+         '&' => "&amp;",
+         '<' => "&lt;",
+         '>' => "&gt;",
+         '"' if quote => "&quot;",
+         _ => continue
+      };
+      w.write_str(&s[last..i])?;
+      w.write_str(entity)?;
+      last = i + 1;
+   }
+   w.write_str(&s[last..])
+}
+
+/// Writes a string escaped as html text content
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::text(&mut s, "a < b && c").unwrap();
+/// assert_eq!(s, "a &lt; b &amp;&amp; c");
+/// ```
+pub fn text<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   escape(w, s, false)
+}
+
+/// Writes a string escaped as the value of a double quoted attribute
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::attribute(&mut s, "say \"hi\"").unwrap();
+/// assert_eq!(s, "say &quot;hi&quot;");
+/// ```
+pub fn attribute<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   escape(w, s, true)
+}
+
+fn escape_xml<W: Write + ?Sized>(w: &mut W, s: &str, quote: bool) -> fmt::Result {
+   let mut last = 0;
+   for (i, c) in s.char_indices() {
+      let entity = match c {
+         '&' => "&amp;",
+         '<' => "&lt;",
+         '>' => "&gt;",
+         '\r' => "&#13;",
+         '"' if quote => "&quot;",
+         '\n' if quote => "&#10;",
+         '\t' if quote => "&#9;",
+         '\t' | '\n' => continue,
+         '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => "\u{fffd}",
+         _ => continue
+      };
+      w.write_str(&s[last..i])?;
+      w.write_str(entity)?;
+      last = i + c.len_utf8();
+   }
+   w.write_str(&s[last..])
+}
+
+/// Writes a string escaped as xml text content
+///
+/// Characters that are not allowed in an xml document are replaced with U+FFFD.
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::xml_text(&mut s, "a < b\u{1}").unwrap();
+/// assert_eq!(s, "a &lt; b\u{fffd}");
+/// ```
+pub fn xml_text<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   escape_xml(w, s, false)
+}
+
+/// Writes a string escaped as the value of a double quoted xml attribute
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::xml_attribute(&mut s, "\"a\"\tb\n").unwrap();
+/// assert_eq!(s, "&quot;a&quot;&#9;b&#10;");
+/// ```
+pub fn xml_attribute<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   escape_xml(w, s, true)
+}
+
+/// Writes a string as the content of a CDATA section
+///
+/// A `]]>` in the content would end the section, so it is split across two sections.
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::cdata(&mut s, "a]]>b").unwrap();
+/// assert_eq!(s, "a]]]]><![CDATA[>b");
+/// ```
+pub fn cdata<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   w.write_str(&s.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Writes a string escaped as the text of a comment
+///
+/// A space is written between any two dashes, so the text cannot contain `--`,
+/// which is needed to end the comment or to open another one.
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::comment(&mut s, "a --> b <!--- c").unwrap();
+/// assert_eq!(s, "a - -> b <!- - - c");
+/// ```
+pub fn comment<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   let mut last = 0;
+   for (i, _) in s.char_indices().filter(|(i, c)| *c == '-' && s[..*i].ends_with('-')) {
+      w.write_str(&s[last..i])?;
+      w.write_str(" ")?;
+      last = i;
+   }
+   w.write_str(&s[last..])
+}
+
+/// Writes a string escaped as a CSS property value
+///
+/// Characters that could end the declaration, open or close a block, or start a string
+/// are replaced with CSS hex escapes. The result still needs attribute escaping.
+///
+/// ```
+/// let mut s = String::new();
+/// rdxl::escape::css(&mut s, "red;} a{color:blue").unwrap();
+/// assert_eq!(s, "red\\3b \\7d  a\\7b color:blue");
+/// ```
+pub fn css<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+   let mut last = 0;
+   for (i, c) in s.char_indices() {
+      match c {
+         ';' | '{' | '}' | '\\' | '"' | '\'' | '<' | '>' => {},
+         c if c.is_control() => {},
+         _ => continue
+      }
+      w.write_str(&s[last..i])?;
+      write!(w, "\\{:x} ", c as u32)?;
+      last = i + c.len_utf8();
+   }
+   w.write_str(&s[last..])
+}
+
+/// A writer adapter that escapes everything written through it as html text
+pub struct Text<'a, W: Write + ?Sized>(pub &'a mut W);
+impl<'a, W: Write + ?Sized> Write for Text<'a, W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      text(self.0, s)
+   }
+}
+
+/// A writer adapter that escapes everything written through it as an attribute value
+pub struct Attribute<'a, W: Write + ?Sized>(pub &'a mut W);
+impl<'a, W: Write + ?Sized> Write for Attribute<'a, W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      attribute(self.0, s)
+   }
+}
+
+/// A writer adapter that escapes everything written through it as a CSS property value
+pub struct Css<'a, W: Write + ?Sized>(pub &'a mut W);
+impl<'a, W: Write + ?Sized> Write for Css<'a, W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      css(self.0, s)
+   }
+}
+
+/// A writer adapter that escapes everything written through it as xml text
+pub struct XmlText<'a, W: Write + ?Sized>(pub &'a mut W);
+impl<'a, W: Write + ?Sized> Write for XmlText<'a, W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      xml_text(self.0, s)
+   }
+}
+
+/// A writer adapter that escapes everything written through it as an xml attribute value
+pub struct XmlAttribute<'a, W: Write + ?Sized>(pub &'a mut W);
+impl<'a, W: Write + ?Sized> Write for XmlAttribute<'a, W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      xml_attribute(self.0, s)
+   }
+}
@@ -0,0 +1,321 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Support for the code generated by the rdxl macros, not a public interface
+//!
+//! Interpolated values and stream targets are dispatched on their type with autoref
+//! specialization: method resolution finds an impl for `T` before auto-referencing to
+//! reach an impl for `&T`, so `RawInterpolate` takes precedence over `EscapedInterpolate`,
+//! `IoTarget` takes precedence over `FmtTarget`, `FlagAttr` takes precedence over `OptionAttr` and
+//! then `PlainAttr`, `IterTokens` takes precedence over `DisplayTokens` and then `IteratorTokens`, and
+//! `OptionStyle` takes precedence over `DisplayStyle`.
+
+use std::cell::Cell;
+use std::fmt::{self, Display, Write};
+use std::io;
+use crate::{escape, RawDisplay};
+use crate::stream::{FmtStream, IoStream};
+
+pub struct Interpolate<'a, T: ?Sized>(pub &'a T);
+
+pub trait RawInterpolate {
+   fn write_text<W: Write>(&self, w: &mut W);
+   fn write_xml<W: Write>(&self, w: &mut W);
+   fn write_xml_attr<W: Write>(&self, w: &mut W);
+}
+impl<'a, T: RawDisplay + ?Sized> RawInterpolate for Interpolate<'a, T> {
+   fn write_text<W: Write>(&self, w: &mut W) {
+      let _ = write!(w, "{}", self.0);
+   }
+   fn write_xml<W: Write>(&self, w: &mut W) {
+      let _ = write!(w, "{}", self.0);
+   }
+   fn write_xml_attr<W: Write>(&self, w: &mut W) {
+      let _ = w.write_str(&self.0.to_string().replace('"', "&quot;"));
+   }
+}
+
+pub trait EscapedInterpolate {
+   fn write_text<W: Write>(&self, w: &mut W);
+   fn write_xml<W: Write>(&self, w: &mut W);
+   fn write_xml_attr<W: Write>(&self, w: &mut W);
+}
+impl<'a, 'b, T: Display + ?Sized> EscapedInterpolate for &'b Interpolate<'a, T> {
+   fn write_text<W: Write>(&self, w: &mut W) {
+      let _ = write!(escape::Text(w), "{}", self.0);
+   }
+   fn write_xml<W: Write>(&self, w: &mut W) {
+      let _ = write!(escape::XmlText(w), "{}", self.0);
+   }
+   fn write_xml_attr<W: Write>(&self, w: &mut W) {
+      let _ = write!(escape::XmlAttribute(w), "{}", self.0);
+   }
+}
+
+/// A writer passed to <b>xhtml_to!</b>, which becomes an IoStream if it implements
+/// `std::io::Write` and a FmtStream otherwise
+pub struct Target<W>(Cell<Option<W>>);
+impl<W> Target<W> {
+   pub fn new(w: W) -> Self {
+      Target(Cell::new(Some(w)))
+   }
+   fn take(&self) -> W {
+      self.0.take().expect("stream target taken twice")
+   }
+}
+
+pub trait IoTarget<W: io::Write> {
+   fn stream(&self) -> IoStream<W>;
+}
+impl<W: io::Write> IoTarget<W> for Target<W> {
+   fn stream(&self) -> IoStream<W> {
+      IoStream::new(self.take())
+   }
+}
+
+pub trait FmtTarget<W: fmt::Write> {
+   fn stream(&self) -> FmtStream<W>;
+}
+impl<W: fmt::Write> FmtTarget<W> for &Target<W> {
+   fn stream(&self) -> FmtStream<W> {
+      FmtStream::new(self.take())
+   }
+}
+
+/// How an expression in attribute position is rendered
+pub enum AttrValue<T> {
+   /// The attribute is left out
+   Omit,
+   /// The attribute is written without a value, as a boolean attribute
+   Flag,
+   /// The attribute is written with the value
+   Value(T),
+}
+
+/// A value interpolated inside of a tag by <b>xhtml_str!</b>, which is escaped as an attribute value
+/// in either kind of quotes, even if it is trusted markup
+pub struct Quoted<'a, T: ?Sized>(pub &'a T);
+impl<'a, T: Display + ?Sized> Display for Quoted<'a, T> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      let mut s = String::new();
+      write!(escape::Attribute(&mut s), "{}", self.0)?;
+      f.write_str(&s.replace('\'', "&#39;"))
+   }
+}
+impl<'a, T: Display + ?Sized> RawDisplay for Quoted<'a, T> {}
+
+/// A value in attribute position, and whether the attribute is one of the boolean attributes of html,
+/// which are written as a flag when true and left out when false
+pub struct Attr<'a, T: ?Sized>(pub &'a T, pub bool);
+impl<'a, T: ?Sized> Clone for Attr<'a, T> {
+   fn clone(&self) -> Self {
+      *self
+   }
+}
+impl<'a, T: ?Sized> Copy for Attr<'a, T> {}
+
+pub trait FlagAttr<'a> {
+   type Value: ?Sized;
+   fn attr_value(self) -> AttrValue<&'a Self::Value>;
+}
+impl<'a> FlagAttr<'a> for &&Attr<'a, bool> {
+   type Value = bool;
+   fn attr_value(self) -> AttrValue<&'a bool> {
+      match (self.1, *self.0) {
+         (false, _) => AttrValue::Value(self.0),
+         (true, true) => AttrValue::Flag,
+         (true, false) => AttrValue::Omit,
+      }
+   }
+}
+impl<'a> FlagAttr<'a> for &&Attr<'a, Option<bool>> {
+   type Value = bool;
+   fn attr_value(self) -> AttrValue<&'a bool> {
+      match self.0 {
+         Some(v) => (&&Attr(v, self.1)).attr_value(),
+         None => AttrValue::Omit,
+      }
+   }
+}
+
+pub trait OptionAttr<'a> {
+   type Value: ?Sized;
+   fn attr_value(self) -> AttrValue<&'a Self::Value>;
+}
+impl<'a, T> OptionAttr<'a> for &Attr<'a, Option<T>> {
+   type Value = T;
+   fn attr_value(self) -> AttrValue<&'a T> {
+      match self.0 {
+         Some(v) => AttrValue::Value(v),
+         None => AttrValue::Omit,
+      }
+   }
+}
+
+pub trait PlainAttr<'a> {
+   type Value: ?Sized;
+   fn attr_value(self) -> AttrValue<&'a Self::Value>;
+}
+impl<'a, T: ?Sized> PlainAttr<'a> for Attr<'a, T> {
+   type Value = T;
+   fn attr_value(self) -> AttrValue<&'a T> {
+      AttrValue::Value(self.0)
+   }
+}
+
+/// A space separated attribute value, such as a list of classes, without duplicates
+#[derive(Default)]
+pub struct TokenList(Vec<String>);
+impl TokenList {
+   pub fn new() -> Self {
+      TokenList(Vec::new())
+   }
+   pub fn push_str(&mut self, s: &str) {
+      for t in s.split_whitespace() {
+         if !self.0.iter().any(|u| u == t) {
+            self.0.push(t.to_string());
+         }
+      }
+   }
+   pub fn is_empty(&self) -> bool {
+      self.0.is_empty()
+   }
+   pub fn write_to<W: Write>(&self, w: &mut W) {
+      let _ = escape::attribute(w, &self.0.join(" "));
+   }
+}
+
+pub struct Tokens<'a, T: ?Sized>(pub &'a T);
+impl<'a, T: ?Sized> Clone for Tokens<'a, T> {
+   fn clone(&self) -> Self {
+      *self
+   }
+}
+impl<'a, T: ?Sized> Copy for Tokens<'a, T> {}
+
+pub trait IterTokens {
+   fn push_to(self, list: &mut TokenList);
+}
+impl<'a, T: ?Sized> IterTokens for &&Tokens<'a, T> where &'a T: IntoIterator, <&'a T as IntoIterator>::Item: Display {
+   fn push_to(self, list: &mut TokenList) {
+      for t in self.0 {
+         list.push_str(&t.to_string());
+      }
+   }
+}
+
+pub trait DisplayTokens {
+   fn push_to(self, list: &mut TokenList);
+}
+impl<'a, T: Display + ?Sized> DisplayTokens for &Tokens<'a, T> {
+   fn push_to(self, list: &mut TokenList) {
+      list.push_str(&self.0.to_string());
+   }
+}
+
+pub trait IteratorTokens {
+   fn push_to(self, list: &mut TokenList);
+}
+impl<'a, T: Iterator + Clone> IteratorTokens for Tokens<'a, T> where T::Item: Display {
+   fn push_to(self, list: &mut TokenList) {
+      for t in self.0.clone() {
+         list.push_str(&t.to_string());
+      }
+   }
+}
+
+/// The declarations of a structured style attribute
+#[derive(Default)]
+pub struct StyleList(String);
+impl StyleList {
+   pub fn new() -> Self {
+      StyleList(String::new())
+   }
+   pub fn push<T: Display>(&mut self, key: &str, value: T) {
+      if !self.0.is_empty() {
+         self.0.push(';');
+      }
+      self.0.push_str(key);
+      self.0.push(':');
+      let _ = write!(escape::Css(&mut self.0), "{}", value);
+   }
+   pub fn is_empty(&self) -> bool {
+      self.0.is_empty()
+   }
+   pub fn write_to<W: Write>(&self, w: &mut W) {
+      let _ = escape::attribute(w, &self.0);
+   }
+}
+
+pub struct Style<T>(pub T);
+
+pub trait OptionStyle {
+   fn push_to(self, key: &str, list: &mut StyleList);
+}
+impl<T: Display> OptionStyle for Style<&Option<T>> {
+   fn push_to(self, key: &str, list: &mut StyleList) {
+      if let Some(v) = self.0 {
+         list.push(key, v);
+      }
+   }
+}
+
+pub trait DisplayStyle {
+   fn push_to(self, key: &str, list: &mut StyleList);
+}
+impl<T: Display> DisplayStyle for &Style<T> {
+   fn push_to(self, key: &str, list: &mut StyleList) {
+      list.push(key, &self.0);
+   }
+}
+
+/// The attributes of a tag with spread attributes, where a later attribute replaces an
+/// earlier attribute of the same name
+#[derive(Default)]
+pub struct AttrList(Vec<(String, String)>);
+impl AttrList {
+   pub fn new() -> Self {
+      AttrList(Vec::new())
+   }
+   /// Sets an attribute already rendered as ` key="value"` or ` key`, or leaves it out if empty
+   pub fn set(&mut self, key: &str, rendered: String) {
+      if rendered.is_empty() {
+         return;
+      }
+      match self.0.iter_mut().find(|(k,_)| k.eq_ignore_ascii_case(key)) {
+         Some(a) => { a.1 = rendered; }
+         None => { self.0.push((key.to_string(), rendered)); }
+      }
+   }
+   /// Sets an attribute from a spread pair, skipping names that are not valid attribute names
+   pub fn spread<K: Display, V: Display>(&mut self, key: K, value: V) {
+      self.spread_escaped(key, value, false);
+   }
+   /// Sets an attribute from a spread pair in <b>xml!</b>, whose value is escaped with the xml rules
+   pub fn spread_xml<K: Display, V: Display>(&mut self, key: K, value: V) {
+      self.spread_escaped(key, value, true);
+   }
+   fn spread_escaped<K: Display, V: Display>(&mut self, key: K, value: V, xml: bool) {
+      let key = key.to_string();
+      let invalid = |c: char| c.is_whitespace() || c.is_control() || "\"'<>/=&".contains(c);
+      if key.is_empty() || key.contains(invalid) {
+         return;
+      }
+      //a valid name has nothing to escape
+      let mut rendered = format!(" {}=\"", key);
+      if xml {
+         let _ = write!(escape::XmlAttribute(&mut rendered), "{}", value);
+      } else {
+         let _ = write!(escape::Attribute(&mut rendered), "{}", value);
+      }
+      rendered.push('"');
+      self.set(&key, rendered);
+   }
+   pub fn write_to<W: Write>(&self, w: &mut W) {
+      for (_,rendered) in self.0.iter() {
+         let _ = w.write_str(rendered);
+      }
+   }
+}
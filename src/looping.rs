@@ -0,0 +1,66 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::iter::Peekable;
+
+/// The position of an item in a template for loop, bound with `for x in xs with l`
+///
+/// ```
+/// # use rdxl::xtext;
+/// let xs = ["a", "b", "c"];
+/// assert_eq!(
+///    xtext!({{ for x in xs.iter() with l {{ {{ l.index() }}{{ x }}{{ if l.is_last() {{ ! }} }} }} }}),
+///    "0a1b2c!"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Loop {
+   index: usize,
+   last: bool,
+}
+impl Loop {
+   /// Pairs each item of an iterator with its position
+   pub fn over<I: IntoIterator>(iter: I) -> LoopIter<I::IntoIter> {
+      LoopIter {
+         iter: iter.into_iter().peekable(),
+         index: 0,
+      }
+   }
+   /// The index of the item, starting from zero
+   pub fn index(&self) -> usize {
+      self.index
+   }
+   pub fn is_first(&self) -> bool {
+      self.index == 0
+   }
+   pub fn is_last(&self) -> bool {
+      self.last
+   }
+   /// Whether the index is even, so the first item is even
+   pub fn is_even(&self) -> bool {
+      !self.is_odd()
+   }
+   pub fn is_odd(&self) -> bool {
+      self.index % 2 == 1
+   }
+}
+
+/// An iterator of items with their Loop positions
+pub struct LoopIter<I: Iterator> {
+   iter: Peekable<I>,
+   index: usize,
+}
+impl<I: Iterator> Iterator for LoopIter<I> {
+   type Item = (Loop, I::Item);
+   fn next(&mut self) -> Option<Self::Item> {
+      let item = self.iter.next()?;
+      let l = Loop {
+         index: self.index,
+         last: self.iter.peek().is_none(),
+      };
+      self.index += 1;
+      Some((l, item))
+   }
+}
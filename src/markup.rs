@@ -0,0 +1,81 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt::{self, Display};
+
+/// A Display type whose output is already markup
+///
+/// Values of these types are written as is when interpolated with {{double braces}},
+/// whereas any other Display value is escaped first. Types rendered with
+/// <b>xrender!</b> or <b>xtextrender!</b> implement this trait automatically.
+pub trait RawDisplay: Display {}
+
+impl<T: RawDisplay + ?Sized> RawDisplay for &T {}
+impl<T: RawDisplay + ?Sized> RawDisplay for Box<T> {}
+
+/// Wraps a value whose Display output is trusted markup, so that it is not escaped
+///
+/// ```
+/// # use rdxl::{xhtml,PreEscaped};
+/// let b = "<b>bold</b>";
+/// assert_eq!(
+///    xhtml!({{ b }} {{ PreEscaped(b) }}),
+///    "&lt;b&gt;bold&lt;/b&gt; <b>bold</b>"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PreEscaped<T>(pub T);
+
+impl<T: Display> Display for PreEscaped<T> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      self.0.fmt(f)
+   }
+}
+impl<T: Display> RawDisplay for PreEscaped<T> {}
+
+/// Rendered markup, as returned by <b>xmarkup!</b>
+///
+/// ```
+/// # use rdxl::{xhtml,xmarkup,Markup};
+/// let m: Markup = xmarkup!(<b>bold</b>);
+/// assert_eq!(m, "<b>bold</b>");
+/// assert_eq!(xhtml!(<p>{{ m }}</p>), "<p><b>bold</b></p>");
+/// ```
+pub type Markup = PreEscaped<String>;
+
+impl Markup {
+   /// Consumes the markup, returning the rendered String
+   pub fn into_string(self) -> String {
+      self.0
+   }
+}
+
+impl From<Markup> for String {
+   fn from(m: Markup) -> String {
+      m.0
+   }
+}
+
+impl<T: AsRef<str>> AsRef<str> for PreEscaped<T> {
+   fn as_ref(&self) -> &str {
+      self.0.as_ref()
+   }
+}
+
+impl<T: AsRef<str>> PartialEq<str> for PreEscaped<T> {
+   fn eq(&self, other: &str) -> bool {
+      self.0.as_ref() == other
+   }
+}
+impl<'a, T: AsRef<str>> PartialEq<&'a str> for PreEscaped<T> {
+   fn eq(&self, other: &&'a str) -> bool {
+      self.0.as_ref() == *other
+   }
+}
+impl<T: AsRef<str>> PartialEq<String> for PreEscaped<T> {
+   fn eq(&self, other: &String) -> bool {
+      self.0.as_ref() == other
+   }
+}
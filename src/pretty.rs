@@ -0,0 +1,209 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Pretty printing of rendered markup, for debugging and golden files
+//!
+//! Each block element is written on its own line, indented by two spaces for each
+//! level of nesting. Text and inline elements are kept together on one line as they
+//! were rendered, except that whitespace at the ends of the line is trimmed. The
+//! contents of `pre`, `textarea`, `script` and `style` are written as is.
+//!
+//! ```
+//! let html = "<div><h1>Title</h1> <p>Some <b>bold</b> text</p><pre> a\n  b</pre></div>";
+//! assert_eq!(rdxl::pretty::pretty(html),
+//!    "<div>\n  <h1>Title</h1>\n  <p>Some <b>bold</b> text</p>\n  <pre> a\n  b</pre>\n</div>\n");
+//! ```
+
+use std::fmt::{self, Display};
+
+const VOID: [&str; 14] = ["area","base","br","col","embed","hr","img",
+   "input","link","meta","param","source","track","wbr"];
+const INLINE: [&str; 33] = ["a","abbr","b","bdi","bdo","br","button","cite","code","data",
+   "del","dfn","em","i","img","input","ins","kbd","label","mark","q","s","samp","select",
+   "small","span","strong","sub","sup","textarea","time","u","var"];
+const VERBATIM: [&str; 4] = ["pre","textarea","script","style"];
+
+enum Node<'a> {
+   Text(&'a str),
+   Element { name: String, open: &'a str, children: Vec<Node<'a>>, close: &'a str },
+   //comments, doctypes and end tags that close nothing
+   Other(&'a str),
+}
+
+impl<'a> Node<'a> {
+   fn is_inline(&self) -> bool {
+      match self {
+         Node::Text(_) => { true },
+         Node::Element { name, children, .. } => {
+            INLINE.contains(&name.as_str()) && children.iter().all(|c| c.is_inline())
+         },
+         Node::Other(_) => { false },
+      }
+   }
+   fn write_inline(&self, out: &mut String) {
+      match self {
+         Node::Text(s) | Node::Other(s) => { out.push_str(s); },
+         Node::Element { open, children, close, .. } => {
+            out.push_str(open);
+            for c in children.iter() { c.write_inline(out); }
+            out.push_str(close);
+         },
+      }
+   }
+   fn write_block(&self, depth: usize, out: &mut String) {
+      match self {
+         Node::Text(s) | Node::Other(s) => { write_line(s, depth, out); },
+         Node::Element { name, open, children, close } => {
+            if VERBATIM.contains(&name.as_str()) || children.iter().all(|c| c.is_inline()) {
+               let mut line = String::new();
+               for c in children.iter() { c.write_inline(&mut line); }
+               let line = if VERBATIM.contains(&name.as_str()) { &line[..] } else { line.trim() };
+               write_line(&format!("{}{}{}", open, line, close), depth, out);
+            } else {
+               write_line(open, depth, out);
+               write_nodes(children, depth + 1, out);
+               write_line(close, depth, out);
+            }
+         },
+      }
+   }
+}
+
+fn write_line(line: &str, depth: usize, out: &mut String) {
+   let line = line.trim();
+   if line.is_empty() { return; }
+   for _ in 0..depth { out.push_str("  "); }
+   out.push_str(line);
+   out.push('\n');
+}
+
+fn write_nodes(nodes: &[Node], depth: usize, out: &mut String) {
+   let mut run = String::new();
+   for n in nodes.iter() {
+      if n.is_inline() {
+         n.write_inline(&mut run);
+      } else {
+         write_line(&run, depth, out);
+         run.clear();
+         n.write_block(depth, out);
+      }
+   }
+   write_line(&run, depth, out);
+}
+
+/// The length of a tag, where a > inside of quotes does not end it
+fn tag_len(s: &str) -> usize {
+   let mut quote = None;
+   for (i,c) in s.char_indices() {
+      match (quote, c) {
+         (None, '"') | (None, '\'') => { quote = Some(c); },
+         (Some(q), c) if q == c => { quote = None; },
+         (None, '>') => { return i + 1; },
+         _ => {},
+      }
+   }
+   s.len()
+}
+
+fn tag_name(tag: &str) -> String {
+   tag.trim_start_matches(['<', '/'])
+      .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+      .next().unwrap_or("").to_ascii_lowercase()
+}
+
+fn parse(html: &str) -> Vec<Node<'_>> {
+   //the open elements, with their names, start tags and children so far
+   let mut stack: Vec<(String, &str, Vec<Node>)> = Vec::new();
+   let mut nodes: Vec<Node> = Vec::new();
+   let mut pos = 0;
+
+   fn close<'a>(stack: &mut Vec<(String, &'a str, Vec<Node<'a>>)>, nodes: &mut Vec<Node<'a>>, end: &'a str) {
+      if let Some((name, open, children)) = stack.pop() {
+         let parent = match stack.last_mut() { Some((_,_,cs)) => { cs }, None => { nodes } };
+         parent.push(Node::Element { name, open, children, close: end });
+      }
+   }
+   fn push<'a>(stack: &mut Vec<(String, &'a str, Vec<Node<'a>>)>, nodes: &mut Vec<Node<'a>>, n: Node<'a>) {
+      match stack.last_mut() { Some((_,_,cs)) => { cs.push(n); }, None => { nodes.push(n); } }
+   }
+
+   while pos < html.len() {
+      let rest = &html[pos..];
+      let next = rest.chars().nth(1).unwrap_or(' ');
+      if !rest.starts_with('<') || !(next.is_alphabetic() || next == '/' || next == '!' || next == '?') {
+         let first = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+         let len = rest[first..].find('<').map(|i| i + first).unwrap_or(rest.len());
+         push(&mut stack, &mut nodes, Node::Text(&rest[..len]));
+         pos += len;
+      } else if rest.starts_with("<!--") {
+         let len = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+         push(&mut stack, &mut nodes, Node::Other(&rest[..len]));
+         pos += len;
+      } else if next == '!' || next == '?' {
+         let len = tag_len(rest);
+         push(&mut stack, &mut nodes, Node::Other(&rest[..len]));
+         pos += len;
+      } else if next == '/' {
+         let len = tag_len(rest);
+         let name = tag_name(&rest[..len]);
+         if stack.iter().any(|(n,_,_)| *n == name) {
+            //elements left open inside of this one are closed with it
+            while stack.last().map(|(n,_,_)| *n != name).unwrap_or(false) {
+               close(&mut stack, &mut nodes, "");
+            }
+            close(&mut stack, &mut nodes, &rest[..len]);
+         } else {
+            push(&mut stack, &mut nodes, Node::Other(&rest[..len]));
+         }
+         pos += len;
+      } else {
+         let len = tag_len(rest);
+         let open = &rest[..len];
+         let name = tag_name(open);
+         pos += len;
+         if open.ends_with("/>") || VOID.contains(&name.as_str()) {
+            push(&mut stack, &mut nodes, Node::Element { name, open, children: Vec::new(), close: "" });
+         } else if VERBATIM.contains(&name.as_str()) {
+            let rest = &html[pos..];
+            let end = format!("</{}", name);
+            let inner = rest.to_ascii_lowercase().find(&end).unwrap_or(rest.len());
+            let len = inner + rest[inner..].find('>').map(|i| i + 1).unwrap_or(rest.len() - inner);
+            let children = if inner > 0 { vec![Node::Text(&rest[..inner])] } else { Vec::new() };
+            push(&mut stack, &mut nodes, Node::Element { name, open, children, close: &rest[inner..len] });
+            pos += len;
+         } else {
+            stack.push((name, open, Vec::new()));
+         }
+      }
+   }
+   while !stack.is_empty() {
+      close(&mut stack, &mut nodes, "");
+   }
+   nodes
+}
+
+/// Formats rendered markup with one block element per line
+pub fn pretty(html: &str) -> String {
+   let mut out = String::with_capacity(html.len() * 2);
+   write_nodes(&parse(html), 0, &mut out);
+   out
+}
+
+/// Displays a value with its rendered markup pretty printed
+///
+/// ```
+/// # use rdxl::{xrender,pretty::Pretty};
+/// struct Card { title: String }
+/// xrender!(Card, <div><h2>{{ self.title }}</h2></div>);
+///
+/// let card = Card { title: "Hello".to_string() };
+/// assert_eq!(Pretty(&card).to_string(), "<div>\n  <h2>Hello</h2>\n</div>\n");
+/// ```
+pub struct Pretty<T: Display>(pub T);
+impl<T: Display> Display for Pretty<T> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      f.write_str(&pretty(&self.0.to_string()))
+   }
+}
@@ -1,4 +1,5 @@
-use std::time::{Instant};
+use std::time::{Instant, Duration};
+use rdxl::xhtml;
 
 struct Display1 {}
 impl std::fmt::Display for Display1 {
@@ -21,7 +22,45 @@ impl std::fmt::Display for Display2 {
    }
 }
 
+struct Row {
+   id: u64,
+   name: &'static str,
+   score: f64,
+}
+
+//the same page as xhtml! expands it with a push_str for each piece of static text
+fn page_unmerged(rows: &[Row]) -> String {
+   rdxl::xhtml_unmerged!(<html><head><title>Report</title></head><body>
+      <table class="report">{{ for r in rows.iter() {{
+         <tr><td class="id">{{ r.id }}</td><td>{{ r.name }}</td><td>{{ r.score }}</td></tr>
+      }} }}</table>
+   </body></html>)
+}
+
+fn page_merged(rows: &[Row]) -> String {
+   xhtml!(<html><head><title>Report</title></head><body>
+      <table class="report">{{ for r in rows.iter() {{
+         <tr><td class="id">{{ r.id }}</td><td>{{ r.name }}</td><td>{{ r.score }}</td></tr>
+      }} }}</table>
+   </body></html>)
+}
+
+fn time<F: Fn() -> String>(f: F) -> Duration {
+   let start = Instant::now();
+   for _ in 0..1000 {
+      let _ = f();
+   }
+   start.elapsed()
+}
+
 fn main() {
+   let rows: Vec<Row> = (0..1000).map(|i| Row { id: i, name: "a & b", score: i as f64 / 7.0 }).collect();
+   assert_eq!(page_unmerged(&rows), page_merged(&rows));
+   let unmerged = time(|| page_unmerged(&rows));
+   println!("page with a push per piece of static text: {}.{:03}s", unmerged.as_secs(), unmerged.subsec_millis());
+   let merged = time(|| page_merged(&rows));
+   println!("page with merged static text: {}.{:03}s", merged.as_secs(), merged.subsec_millis());
+
    let start = Instant::now();
    let _ = Display1{}.to_string();
    println!("many Formatter writes: {}.{}s", start.elapsed().as_secs(), start.elapsed().subsec_millis());
@@ -30,5 +69,3 @@ fn main() {
    let _ = Display2{}.to_string();
    println!("many String writes: {}.{}s", start.elapsed().as_secs(), start.elapsed().subsec_millis());
 }
-
-
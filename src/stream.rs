@@ -0,0 +1,87 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Streams that render markup directly into a writer
+//!
+//! The code generated by the rdxl macros writes to a stream with `push_str` and `push`.
+//! A String is the default stream. The streams in this module forward each write to a
+//! `std::fmt::Write` or `std::io::Write` sink instead. Write errors cannot interrupt the
+//! generated code, so the first error is remembered, later writes are skipped, and the
+//! error is returned from `finish`.
+//!
+//! These streams are created by <b>xhtml_to!</b> and <b>xtext_to!</b>. When writing to a file
+//! or socket, wrap it in a `std::io::BufWriter` because every static fragment is a separate write.
+
+use std::fmt;
+use std::io;
+
+/// A stream over a `std::fmt::Write` sink
+pub struct FmtStream<W: fmt::Write> {
+   writer: W,
+   result: fmt::Result,
+}
+
+impl<W: fmt::Write> FmtStream<W> {
+   pub fn new(writer: W) -> Self {
+      FmtStream { writer, result: Ok(()) }
+   }
+   pub fn push_str(&mut self, s: &str) {
+      if self.result.is_ok() {
+         self.result = self.writer.write_str(s);
+      }
+   }
+   pub fn push(&mut self, c: char) {
+      if self.result.is_ok() {
+         self.result = self.writer.write_char(c);
+      }
+   }
+   /// Returns the first error that occurred while writing, if any
+   pub fn finish(self) -> fmt::Result {
+      self.result
+   }
+}
+
+impl<W: fmt::Write> fmt::Write for FmtStream<W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      self.push_str(s);
+      self.result
+   }
+}
+
+/// A stream over a `std::io::Write` sink
+pub struct IoStream<W: io::Write> {
+   writer: W,
+   error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoStream<W> {
+   pub fn new(writer: W) -> Self {
+      IoStream { writer, error: None }
+   }
+   pub fn push_str(&mut self, s: &str) {
+      if self.error.is_none() {
+         if let Err(e) = self.writer.write_all(s.as_bytes()) {
+            self.error = Some(e);
+         }
+      }
+   }
+   pub fn push(&mut self, c: char) {
+      self.push_str(c.encode_utf8(&mut [0; 4]));
+   }
+   /// Returns the first error that occurred while writing, if any
+   pub fn finish(self) -> io::Result<()> {
+      match self.error {
+         Some(e) => Err(e),
+         None => Ok(()),
+      }
+   }
+}
+
+impl<W: io::Write> fmt::Write for IoStream<W> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      self.push_str(s);
+      if self.error.is_some() { Err(fmt::Error) } else { Ok(()) }
+   }
+}
@@ -1 +0,0 @@
-pub use rdxl_internals::xtext::*;
@@ -0,0 +1,55 @@
+use rdxl::{xhtml,xtext,xtype,xrender};
+
+xtype!(<!Chip classes:Vec<String>/>);
+xrender!(Chip, <span class=["chip", self.classes]>c</span>);
+
+#[test]
+fn class_list1() {
+   let selected = true;
+   let disabled = false;
+   assert_eq!(
+      xtext!(<button class=["btn", "active" if selected, "off" if disabled]>Go</button>),
+      "<button class=\"btn active\">Go</button>"
+   );
+}
+
+#[test]
+fn class_list2() {
+   let extra = vec!["wide", "btn"];
+   let size = "big";
+   let hint: Option<&str> = None;
+   assert_eq!(
+      xtext!(<div class=["btn primary", extra, size, hint, "wide"]/>),
+      "<div class=\"btn primary wide big\"></div>"
+   );
+}
+
+#[test]
+fn class_list3() {
+   let on = false;
+   let none: Vec<String> = Vec::new();
+   assert_eq!(xhtml!(<p class=["a" if on, none] id="x">b</p>), "<p id=\"x\">b</p>");
+   assert_eq!(xhtml!(<p class=[]/>), "<p></p>");
+}
+
+#[test]
+fn class_list4() {
+   let evil = "a\"b<c";
+   assert_eq!(xtext!(<p class=[evil, 1 + 1]/>), "<p class=\"a&quot;b&lt;c 2\"></p>");
+}
+
+#[test]
+fn class_list5() {
+   let list = ["a", "b"];
+   assert_eq!(xtext!(<p class=[list]/>), "<p class=\"a b\"></p>");
+}
+
+#[test]
+fn class_list6() {
+   let classes = vec!["big".to_string(), "chip".to_string()];
+   assert_eq!(xtext!(<!Chip classes={{ classes }}/>), "<span class=\"chip big\">c</span>");
+   let extra = vec!["x"];
+   let names = ["a", "b"];
+   assert_eq!(xtext!({{ for n in names.iter() {{<i class=[n, extra]/>}} }}), "<i class=\"a x\"></i><i class=\"b x\"></i>");
+   assert_eq!(xtext!(<p class=[names.iter().map(|n| n.to_uppercase())]/>), "<p class=\"A B\"></p>");
+}
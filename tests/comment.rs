@@ -0,0 +1,26 @@
+use rdxl::{xhtml,xtext,xhtml_min,html};
+
+#[test]
+fn comment1() {
+   let x = 5;
+   assert_eq!(xhtml!(<p><!-- static note --></p>), "<p><!-- static note --></p>");
+   assert_eq!(xhtml!(<!--a > b-->), "<!-- a > b -->");
+   assert_eq!(xhtml!(<!-- x is {{ x }} -->), "<!-- x is 5 -->");
+   assert_eq!(xtext!(<p> <!-- x is {{ x }} --> </p>), "<p><!-- x is 5 --></p>");
+}
+
+#[test]
+fn comment2() {
+   let evil = "--><script>alert(1)</script><!--";
+   assert_eq!(xhtml!(<!-- {{ evil }} -->), "<!-- - -><script>alert(1)</script><!- - -->");
+   let dash = "-";
+   assert_eq!(xhtml!(<!-- a-{{ dash }}> -->), "<!-- a- -> -->");
+}
+
+#[test]
+fn comment3() {
+   let x = 1;
+   assert_eq!(xhtml!(<p>a {{# a note for {{ x }} }} b{{# another }}</p>), "<p>a b</p>");
+   assert_eq!(xhtml_min!(<div> a <!-- note --> b </div><!-- {{ x }} --><p>c</p>), "<div>a b</div><p>c</p>");
+   assert_eq!(html!(<!-- generated --><html></html>), "<!DOCTYPE html><!-- generated --><html></html>");
+}
@@ -0,0 +1,35 @@
+use rdxl::{xhtml,xtext};
+
+#[test]
+fn dashed_attr1() {
+   let id = 7;
+   assert_eq!(
+      xhtml!(<div data-id={{ id }} aria-label="close" hx-get="/a" data-x-1="y"/>),
+      "<div data-id=\"7\" aria-label=\"close\" hx-get=\"/a\" data-x-1=\"y\"></div>"
+   );
+}
+
+#[test]
+fn dashed_attr2() {
+   let show = true;
+   assert_eq!(
+      xtext!(<input data-type="text" {{ if show {{ aria-hidden="false" }} }} data-on/>),
+      "<input data-type=\"text\" aria-hidden=\"false\" data-on/>"
+   );
+}
+
+#[test]
+fn dashed_tag1() {
+   assert_eq!(
+      xhtml!(<my-widget x-data="{}"><my-inner-part/></my-widget>),
+      "<my-widget x-data=\"{}\"><my-inner-part></my-inner-part></my-widget>"
+   );
+}
+
+#[test]
+fn dashed_text1() {
+   assert_eq!(
+      xhtml!(<p>a - b c-d</p>),
+      "<p>a - b c-d</p>"
+   );
+}
@@ -0,0 +1,58 @@
+use rdxl::{xhtml,xtext,xtype,xrender,PreEscaped};
+
+xtype!(<!Bold text:String/>);
+xrender!(Bold, <b>{{ self.text }}</b>);
+
+#[test]
+fn escape_text1() {
+   let x = "<script>alert('&')</script>";
+   assert_eq!(
+      xhtml!(<p>{{ x }}</p>),
+      "<p>&lt;script&gt;alert('&amp;')&lt;/script&gt;</p>"
+   );
+}
+
+#[test]
+fn escape_text2() {
+   let x = "\"quoted\"";
+   assert_eq!(
+      xtext!(<p>{{ x }}</p>),
+      "<p>\"quoted\"</p>"
+   );
+}
+
+#[test]
+fn escape_attr1() {
+   let x = "\"><script>";
+   assert_eq!(
+      xhtml!(<a title={{ x }}>link</a>),
+      "<a title=\"&quot;&gt;&lt;script&gt;\">link</a>"
+   );
+}
+
+#[test]
+fn escape_attr2() {
+   let x = "a&b";
+   assert_eq!(
+      xtext!(<a {{ if true {{ title={{ x }} }} }}>"link"</a>),
+      "<a title=\"a&amp;b\">link</a>"
+   );
+}
+
+#[test]
+fn escape_raw1() {
+   let x = "<br/>";
+   assert_eq!(
+      xhtml!({{ PreEscaped(x) }}),
+      "<br/>"
+   );
+}
+
+#[test]
+fn escape_component1() {
+   let b = Bold { text: "<i>".to_string(), children: vec![] };
+   assert_eq!(
+      xhtml!(<p>{{ b }}</p>),
+      "<p><b>&lt;i&gt;</b></p>"
+   );
+}
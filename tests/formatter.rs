@@ -27,6 +27,6 @@ fn formatter2(){
 fn formatter3(){
    assert_eq!(
       xhtml!(<div an_attr={{ "\"" }}>dave</div>),
-      r#"<div an_attr="\"">dave</div>"#.to_string()
+      r#"<div an_attr="&quot;">dave</div>"#.to_string()
    );
 }
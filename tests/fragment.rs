@@ -0,0 +1,34 @@
+use rdxl::{xhtml,xtext,xmarkup,xtype,xtextrender,Markup};
+
+fn cells(a: &str, b: &str) -> Markup {
+   xmarkup!(<><td>{{ a }}</td><td>{{ b }}</td></>)
+}
+
+#[test]
+fn fragment1() {
+   assert_eq!(xtext!(<tr>{{ cells("1", "<2>") }}</tr>), "<tr><td>1</td><td>&lt;2&gt;</td></tr>");
+   assert_eq!(xtext!(<p><><b>a</b><i>b</i></></p>), "<p><b>a</b><i>b</i></p>");
+   assert_eq!(xtext!(<></>), "");
+}
+
+#[test]
+fn fragment2() {
+   let c = "c";
+   assert_eq!(xhtml!(<p>a <><b>b</b> {{ c }}</> d</p>), "<p>a <b>b</b> c d</p>");
+   assert_eq!(xhtml!(<p>a<><b>b</b></>d</p>), "<p>a<b>b</b>d</p>");
+}
+
+xtype!(<!Panel label:Markup>
+   <?/>
+</Panel>);
+xtextrender!(Panel, <section><h2>{{ self.label }}</h2>{{ for c in self.children.iter() {{
+   {{ match c {{ PanelChildren::Display(d) => {{ {{ d }} }} }} }}
+}} }}</section>);
+
+#[test]
+fn fragment3() {
+   assert_eq!(
+      xtext!(<!Panel label=<><i>x</i>y</>><><b>1</b><b>2</b></></Panel>),
+      "<section><h2><i>x</i>y</h2><b>1</b><b>2</b></section>"
+   );
+}
@@ -0,0 +1,56 @@
+use rdxl::{xhtml,xtext,xtype,xrender,xtextrender,PreEscaped};
+use std::fmt::Display;
+
+xtype!(<!Table<'a, T: Display> rows:&'a [T]>
+   <!Caption<'a> text:&'a str/>
+   <?/>
+</Table>);
+xtextrender!(Table<'a, T: Display>, <table>
+   {{ for c in self.children.iter() {{
+      {{ if let TableChildren::Caption(c) = c {{
+         <caption>{{ c.text }}</caption>
+      }} else if let TableChildren::Display(d) = c {{
+         {{ d }}
+      }} }}
+   }} }}
+   {{ for r in self.rows.iter() {{
+      <tr><td>{{ r }}</td></tr>
+   }} }}
+</table>);
+xrender!(Caption<'a>, <caption>{{ self.text }}</caption>);
+
+xtype!(<!Pair<A, B> first:A second:B/>);
+xtextrender!(Pair<A: Display, B: Display>, <p>{{ self.first }}{{ self.second }}</p>);
+
+#[test]
+fn generic_type1() {
+   let rows = vec![1, 2];
+   assert_eq!(
+      xhtml!(<!Table rows={{ &rows }}/>),
+      "<table><tr><td>1</td></tr><tr><td>2</td></tr></table>"
+   );
+}
+
+#[test]
+fn generic_type2() {
+   let title = String::from("a&b");
+   let rows = ["x"];
+   let t = Table {
+      rows: &rows,
+      children: vec![
+         TableChildren::Caption(Caption { text: &title, ..Default::default() }),
+         TableChildren::Display(Box::new(PreEscaped(&title))),
+      ],
+   };
+   assert_eq!(
+      xtext!({{ t }}),
+      "<table><caption>a&amp;b</caption>a&b<tr><td>x</td></tr></table>"
+   );
+}
+
+#[test]
+fn generic_type3() {
+   let p: Pair<u8, char> = Default::default();
+   assert_eq!(p.to_string(), "<p>0\0</p>");
+   assert_eq!(xhtml!(<!Pair first={{ "a" }} second={{ 'b' }}/>), "<p>ab</p>");
+}
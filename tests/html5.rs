@@ -0,0 +1,27 @@
+use rdxl::{html,xhtml};
+
+#[test]
+fn html5_1() {
+   assert_eq!(html!(<script async/>), "<script async></script>");
+   assert_eq!(html!(<p><br/>a<wbr/>b<col/><img src="x.png"/></p>), "<p><br>a<wbr>b<col><img src=\"x.png\"></p>");
+   assert_eq!(xhtml!(<p><br/></p>), "<p><br/></p>");
+}
+
+#[test]
+fn html5_2() {
+   let title = "Home";
+   assert_eq!(html!(
+      <html>
+         <head><meta charset="utf-8"/><title>{{ title }}</title></head>
+         <body><div/><iframe src="/x"/></body>
+      </html>
+   ), "<!DOCTYPE html><html> <head><meta charset=\"utf-8\"><title>Home</title></head> <body><div></div><iframe src=\"/x\"></iframe></body> </html>");
+}
+
+#[test]
+fn html5_3() {
+   let xs = [1, 2];
+   assert_eq!(html!({{ let n = 0; }}<html>{{ n }}</html>), "<!DOCTYPE html><html>0</html>");
+   assert_eq!(html!(<div><html/></div>), "<div><html></html></div>");
+   assert_eq!(html!({{ for x in xs.iter() {{<input value={{ x }}/>}} }}), "<input value=\"1\"> <input value=\"2\"> ");
+}
@@ -0,0 +1,12 @@
+use rdxl::include_xhtml;
+
+#[test]
+fn include1() {
+   let title = "Home";
+   let name = "<world>";
+   let items = [1, 2];
+   assert_eq!(
+      include_xhtml!("tests/templates/page.rdxl"),
+      "<html> <head><title>Home</title></head> <body> <h1 class=\"title\">Hello, &lt;world&gt;!</h1> <ul><li>1</li> <li>2</li> </ul> </body> </html>"
+   );
+}
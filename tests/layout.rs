@@ -0,0 +1,42 @@
+use rdxl::{xlayout,xrender,xhtml};
+
+xlayout!(Base, <html>
+   <head><title>{{ block title {{ Untitled }} }}</title></head>
+   <body>{{ block header {{ <h1>{{ block heading {{ Welcome }} }}</h1> }} }}{{ block body {{ }} }}</body>
+</html>);
+
+struct Blank;
+xrender!(Blank: Base,);
+
+struct Post { title: String, tags: Vec<&'static str> }
+xrender!(Post: Base,
+   {{ block title {{ {{ self.title }} }} }}
+   {{ block heading {{ <em>{{ self.title }}</em> }} }}
+   {{ block body {{
+      <ul>{{ for t in self.tags.iter() {{ <li>{{ t }}</li> }} }}</ul>
+   }} }}
+);
+
+struct Bare;
+xrender!(Bare: Base,
+   {{ block header {{ }} }}
+);
+
+#[test]
+fn layout1() {
+   assert_eq!(Blank.to_string(),
+      "<html> <head><title>Untitled</title></head> <body><h1>Welcome</h1></body> </html>");
+}
+
+#[test]
+fn layout2() {
+   let p = Post { title: "A < B".to_string(), tags: vec!["x", "y"] };
+   assert_eq!(p.to_string(),
+      "<html> <head><title>A &lt; B</title></head> <body><h1><em>A &lt; B</em></h1><ul><li>x</li> <li>y</li> </ul></body> </html>");
+}
+
+#[test]
+fn layout3() {
+   assert_eq!(xhtml!(<div>{{ Bare }}</div>),
+      "<div><html> <head><title>Untitled</title></head> <body></body> </html></div>");
+}
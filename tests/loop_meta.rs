@@ -0,0 +1,62 @@
+use rdxl::{xhtml,xtext};
+
+#[test]
+fn loop_meta1() {
+   let xs = ["a", "b", "c"];
+   assert_eq!(xtext!(
+      <table>{{ for x in xs.iter() with row {{
+         <tr class=["even" if row.is_even(), "odd" if row.is_odd()]>
+            <td>{{ row.index() }}</td><td>{{ x }}</td>
+            {{ if row.is_first() {{ <td>first</td> }} }}
+            {{ if row.is_last() {{ <td>last</td> }} }}
+         </tr>
+      }} }}</table>
+   ), "<table><tr class=\"even\"><td>0</td><td>a</td><td>first</td></tr>\
+<tr class=\"odd\"><td>1</td><td>b</td></tr>\
+<tr class=\"even\"><td>2</td><td>c</td><td>last</td></tr></table>");
+}
+
+#[test]
+fn loop_meta2() {
+   let xs = vec![1, 2, 3];
+   assert_eq!(xtext!(
+      {{ for x in xs.iter() {{ {{ x }} }} }}
+      {{ between {{ , }} }}
+   ), "1,2,3");
+   assert_eq!(xhtml!(
+      <p>{{ for x in xs {{ <b>{{ x }}</b> }} }}
+      {{ between {{ and }} }}</p>
+   ), "<p><b>1</b> and <b>2</b> and <b>3</b> </p>");
+}
+
+#[test]
+fn loop_meta3() {
+   let none: Vec<u8> = Vec::new();
+   let some = [4];
+   assert_eq!(xtext!(
+      <ul>{{ for x in none {{ <li>{{ x }}</li> }} }}
+      {{ between {{ <hr/> }} }}
+      {{ else {{ <li>empty</li> }} }}</ul>
+      <ul>{{ for x in some {{ <li>{{ x }}</li> }} }}
+      {{ else {{ <li>nothing here</li> }} }}</ul>
+   ), "<ul><li>empty</li></ul><ul><li>4</li></ul>");
+   assert_eq!(xhtml!(
+      <ul>{{ for x in some {{ <li>{{ x }}</li> }} }}
+      {{ else {{ <li>nothing here</li> }} }}</ul>
+   ), "<ul><li>4</li> </ul>");
+}
+
+#[test]
+fn loop_meta4() {
+   let grid = [[1, 2], [3, 4]];
+   assert_eq!(xtext!(
+      {{ 'rows: for row in grid with r {{
+         {{ for c in row with l {{
+            {{ if r.is_last() && l.is_last() {{ {{ break 'rows; }} }} }}
+            {{ c }}
+         }} }}
+         {{ between {{ , }} }}
+      }} }}
+      {{ between {{ ; }} }}
+   ), "1,2;3,");
+}
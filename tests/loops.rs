@@ -1,4 +1,4 @@
-use rdxl::xhtml;
+use rdxl::{xhtml,xtext,xtextrender};
 
 fn bs(s: String) -> String {
    s.split_whitespace().collect::<Vec<&str>>().join(" ")
@@ -54,3 +54,69 @@ fn loops4(){
    </div>)),
    "<div> <p>inside loop</p> </div>");
 }
+
+#[test]
+fn loops5(){
+   let mut it = [1, 2, 3, 4].iter().peekable();
+   assert_eq!(xtext!(
+      {{ while let Some(n) = it.next() {{
+         {{ n }}
+         {{ if it.peek().is_some() {{ , }} }}
+      }} }}
+   ), "1,2,3,4");
+}
+
+#[test]
+fn loops6(){
+   let xs = [1, 2, 3, 4, 5];
+   let show = true;
+   assert_eq!(xtext!(
+      {{ for x in xs {{
+         {{ if x % 2 == 0 {{ {{ continue; }} }} }}
+         {{ if show {{ <i>{{ x }}</i> }} }}
+      }} }}
+   ), "<i>1</i><i>3</i><i>5</i>");
+}
+
+#[test]
+fn loops7(){
+   let grid = [[1, 2], [3, 4], [5, 6]];
+   assert_eq!(xtext!(
+      {{ 'rows: for row in grid {{
+         {{ for c in row {{
+            {{ if c == 4 {{ {{ break 'rows; }} }} }}
+            {{ if c == 1 {{ {{ continue 'rows; }} }} }}
+            {{ c }}
+         }} }}
+      }} }}
+   ), "3");
+}
+
+#[test]
+fn loops8(){
+   let mut n = 0;
+   assert_eq!(xtext!(
+      <p>{{ loop {{
+         {{ n += 1; }}
+         {{ if n * n > 50 {{ {{ break n }} }} }}
+      }} }}</p>
+      {{ 'outer: loop {{
+         {{ loop {{ {{ break 'outer "done"; }} }} }}
+      }} }}
+   ), "<p>8</p>done");
+}
+
+struct Countdown { from: u32 }
+xtextrender!(Countdown, <ol>
+   {{ let mut i = self.from; }}
+   {{ loop {{
+      {{ if i == 0 {{ {{ break; }} }} }}
+      <li>{{ i }}</li>
+      {{ i -= 1; }}
+   }} }}
+</ol>);
+
+#[test]
+fn loops9(){
+   assert_eq!(Countdown { from: 2 }.to_string(), "<ol><li>2</li><li>1</li></ol>");
+}
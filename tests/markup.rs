@@ -0,0 +1,56 @@
+use rdxl::{xhtml,xmarkup,xtype,xtextrender,Markup,PreEscaped};
+
+xtype!(<!Panel><?/></Panel>);
+xtextrender!(Panel, <div>
+  {{ for PanelChildren::Display(d) in self.children.iter() {{
+    {{ d }}
+  }} }}
+</div>);
+
+fn item(x: &str) -> Markup {
+   xmarkup!(<li>{{ x }}</li>)
+}
+
+#[test]
+fn markup1() {
+   assert_eq!(
+      xhtml!(<ul>{{ item("<a>") }}{{ item("b") }}</ul>),
+      "<ul><li>&lt;a&gt;</li><li>b</li></ul>"
+   );
+}
+
+#[test]
+fn markup2() {
+   let s: String = xmarkup!(<br/>).into_string();
+   assert_eq!(s, "<br/>");
+   assert_eq!(String::from(xmarkup!(<hr/>)), "<hr/>");
+}
+
+#[test]
+fn markup3() {
+   let s = xhtml!(<b>"x"</b>);
+   assert_eq!(
+      xhtml!({{ s }}{{ PreEscaped(&s) }}),
+      "&lt;b&gt;x&lt;/b&gt;<b>x</b>"
+   );
+}
+
+#[test]
+fn markup4() {
+   let x = "1 < 2";
+   assert_eq!(
+      xhtml!(<!Panel>
+        <?><i>{{ x }}</i></?>
+        <?>{{ item("c") }}</?>
+      </Panel>),
+      "<div><i>1 &lt; 2</i><li>c</li></div>"
+   );
+}
+
+#[test]
+fn markup5() {
+   let mut p = Panel::default();
+   p.children.push(PanelChildren::Display(Box::new(PreEscaped("<hr/>"))));
+   p.children.push(PanelChildren::Display(Box::new(xmarkup!(<br/>))));
+   assert_eq!(xhtml!({{ p }}), "<div><hr/><br/></div>");
+}
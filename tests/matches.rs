@@ -0,0 +1,50 @@
+use rdxl::{xtext,xtype,xtextrender};
+
+xtype!(<!Menu>
+   <!Entry label:String/>
+   <!Divider/>
+</Menu>);
+
+xtextrender!(Menu, <ul>
+  {{ for c in self.children.iter() {{
+    {{ match c {{
+      MenuChildren::Entry(e) => {{ <li>{{ e.label }}</li> }},
+      MenuChildren::Divider(_) => {{ <hr/> }}
+    }} }}
+  }} }}
+</ul>);
+
+#[test]
+fn match1() {
+   assert_eq!(
+      xtext!(<!Menu><!Entry label="a"/><!Divider/><!Entry label="b"/></Menu>),
+      "<ul><li>a</li><hr/><li>b</li></ul>"
+   );
+}
+
+#[test]
+fn match2() {
+   let xs = [Some(1), Some(5), None, Some(7)];
+   assert_eq!(xtext!(
+      {{ for x in xs.iter() {{
+         {{ match x {{
+            Some(1) | Some(2) => {{ small }},
+            Some(n) if *n > 6 => {{ <i>{{ n }}</i> }}
+            Some(n) => {{ <b>{{ n }}</b> }},
+            None => {{ }},
+         }} }}
+      }} }}
+   ), "small<b>5</b><i>7</i>");
+}
+
+#[test]
+fn match3() {
+   struct Point { x: i32, y: i32 }
+   let p = Point { x: 0, y: 2 };
+   assert_eq!(xtext!(
+      {{ match p {{
+         Point { x: 0, y } => {{ <y>{{ y }}</y> }},
+         Point { .. } => {{ elsewhere }}
+      }} }}
+   ), "<y>2</y>");
+}
@@ -0,0 +1,51 @@
+use rdxl::{xhtml,xhtml_min};
+
+#[test]
+fn minify1() {
+   let name = "Ann";
+   assert_eq!(xhtml_min!(<div>
+      <h1> Hello </h1>
+      <p>Welcome <b>back</b> , <a href="/u">{{ name }}</a> !</p>
+      <p>bye</p>
+   </div>), "<div><h1>Hello</h1><p>Welcome <b>back</b> , <a href=/u>Ann</a> !</p><p>bye</p></div>");
+}
+
+#[test]
+fn minify2() {
+   let title = "x y";
+   assert_eq!(xhtml_min!(<input type="text" disabled="" value="a b" data-x="it's" n=3 title={{ title }}/>),
+      "<input type=text disabled value=\"a b\" data-x=\"it's\" n=3 title=\"x y\">");
+}
+
+#[test]
+fn minify3() {
+   let xs = [1, 2];
+   let full = xhtml!(<table>
+      {{ for x in xs.iter() {{
+         <tr> <td>{{ x }}</td> </tr>
+      }} }}
+   </table>);
+   let min = xhtml_min!(<table>
+      {{ for x in xs.iter() {{
+         <tr> <td>{{ x }}</td> </tr>
+      }} }}
+   </table>);
+   assert_eq!(min, "<table><tr><td>1</td></tr><tr><td>2</td></tr></table>");
+   assert!(min.len() < full.len());
+}
+
+#[test]
+fn minify4() {
+   assert_eq!(xhtml_min!(<img src="a.png"/>), "<img src=a.png>");
+   assert_eq!(xhtml_min!(<p><br/><a href="/x/">x</a><iframe src="/f/"/></p>), "<p><br><a href=/x/>x</a><iframe src=/f/></iframe></p>");
+   assert_eq!(xhtml_min!(<input name="name" value="value" selected="selected" checked="" title=""/>),
+      "<input name=name value=value selected checked title=\"\">");
+}
+
+#[test]
+fn minify5() {
+   assert_eq!(xhtml_min!(<p>Hello <my-name>Ann</my-name> and <svg/> bye</p>),
+      "<p>Hello <my-name>Ann</my-name> and <svg></svg> bye</p>");
+   assert_eq!(xhtml_min!(<div> <video src="v"/> <canvas/> </div> <ul> <li> a </li> </ul>),
+      "<div><video src=v></video> <canvas></canvas></div><ul><li>a</li></ul>");
+}
@@ -0,0 +1,44 @@
+use rdxl::{xhtml,xtext,PreEscaped};
+
+#[test]
+fn optional_attr1() {
+   let url: Option<&str> = Some("/a?b=1&c=\"2\"");
+   let none: Option<String> = None;
+   assert_eq!(
+      xhtml!(<a href={{ url }} title={{ none }}>link</a>),
+      "<a href=\"/a?b=1&amp;c=&quot;2&quot;\">link</a>"
+   );
+}
+
+#[test]
+fn optional_attr2() {
+   let id = Some(3);
+   assert_eq!(xtext!(<div id={{ id }} class={{ id.map(|i| i+1) }}/>), "<div id=\"3\" class=\"4\"></div>");
+   assert_eq!(xtext!(<div id={{ None::<u8> }}/>), "<div></div>");
+}
+
+#[test]
+fn bool_attr1() {
+   let busy = true;
+   assert_eq!(
+      xhtml!(<button disabled={{ busy }} hidden={{ !busy }} type="submit">Go</button>),
+      "<button disabled type=\"submit\">Go</button>"
+   );
+   assert_eq!(
+      xhtml!(<input checked={{ 1 > 2 }}/>),
+      "<input/>"
+   );
+}
+
+#[test]
+fn plain_attr1() {
+   assert_eq!(
+      xhtml!(<div data={{ 'x' }} raw={{ PreEscaped("&amp;") }} n={{ Some(false) }}/>),
+      "<div data=\"x\" raw=\"&amp;\" n=\"false\"></div>"
+   );
+   let open = true;
+   assert_eq!(
+      xhtml!(<button aria-expanded={{ open }} aria-hidden={{ !open }} disabled={{ Some(false) }} hidden={{ Some(open) }}/>),
+      "<button aria-expanded=\"true\" aria-hidden=\"false\" hidden></button>"
+   );
+}
@@ -0,0 +1,62 @@
+use rdxl::{xhtml,xhtml_pretty,xrender};
+use rdxl::pretty::{pretty,Pretty};
+
+#[test]
+fn pretty1() {
+   let rows = [(1, "one"), (2, "two")];
+   assert_eq!(xhtml_pretty!(<table>
+      {{ for (n, s) in rows.iter() {{
+         <tr><td>{{ n }}</td><td><a href="#">{{ s }}</a></td></tr>
+      }} }}
+   </table>),
+"<table>
+  <tr>
+    <td>1</td>
+    <td><a href=\"#\">one</a></td>
+  </tr>
+  <tr>
+    <td>2</td>
+    <td><a href=\"#\">two</a></td>
+  </tr>
+</table>
+");
+}
+
+#[test]
+fn pretty2() {
+   assert_eq!(pretty("<!DOCTYPE html><html><body><!-- c --><pre>\n  x <b>y</b>\n</pre>\
+<textarea> a\n b </textarea><p>Go <br/> <img src=\"a>b\"> now</p><script>if (a<b) {}</script></body></html>"),
+"<!DOCTYPE html>
+<html>
+  <body>
+    <!-- c -->
+    <pre>
+  x <b>y</b>
+</pre>
+    <textarea> a
+ b </textarea>
+    <p>Go <br/> <img src=\"a>b\"> now</p>
+    <script>if (a<b) {}</script>
+  </body>
+</html>
+");
+}
+
+struct Item { name: &'static str }
+xrender!(Item, <li><span>{{ self.name }}</span></li>);
+
+#[test]
+fn pretty3() {
+   let items = [Item { name: "a" }, Item { name: "b" }];
+   let list = xhtml!(<div><ul>{{ for i in items.iter() {{ {{ i }} }} }}</ul> <p>after</p></div>);
+   assert_eq!(Pretty(&list).to_string(),
+      "<div>\n  <ul>\n    <li><span>a</span></li>\n    <li><span>b</span></li>\n  </ul>\n  <p>after</p>\n</div>\n");
+   assert_eq!(pretty("<div><p>unclosed</div></span>"), "<div>\n  <p>unclosed\n</div>\n</span>\n");
+}
+
+#[test]
+fn pretty4() {
+   assert_eq!(pretty("<div>é<p>ü < ö</p>日本<br/>ñ</div>"), "<div>\n  é\n  <p>ü < ö</p>\n  日本<br/>ñ\n</div>\n");
+   let text = "line one\n  line two";
+   assert_eq!(xhtml_pretty!(<pre>{{ text }}</pre>), "<pre>line one\n  line two</pre>\n");
+}
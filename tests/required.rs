@@ -0,0 +1,38 @@
+use rdxl::{xhtml,xtype,xrender};
+
+//no Default
+#[derive(Clone, Copy)]
+pub enum Level { Low, High }
+impl std::fmt::Display for Level {
+   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+      f.write_str(match self { Level::Low => "low", Level::High => "high" })
+   }
+}
+
+xtype!(<!Alert level!:Level message!:String dismissable:bool>
+   <!Action label!:&'static str/>
+</Alert>);
+xrender!(Alert, <div class={{ self.level }}>
+   {{ self.message }}
+</div>);
+xrender!(Action, <button>{{ self.label }}</button>);
+
+#[test]
+fn required1() {
+   assert_eq!(
+      xhtml!(<!Alert level={{ Level::High }} message="disk full"/>),
+      "<div class=\"high\"> disk full </div>"
+   );
+}
+
+#[test]
+fn required2() {
+   let a = Alert::builder()
+      .message("m".to_string())
+      .dismissable(true)
+      .level(Level::Low)
+      .build();
+   assert!(a.dismissable);
+   assert_eq!(a.to_string(), "<div class=\"low\"> m </div>");
+   assert_eq!(xhtml!(<!Action label={{ "ok" }}/>), "<button>ok</button>");
+}
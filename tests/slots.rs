@@ -0,0 +1,52 @@
+use rdxl::{xhtml,xtext,xtype,xrender,xtextrender,Markup};
+
+xtype!(<!Card title:String>
+   <slot:header/>
+   <slot:footer/>
+   <?/>
+</Card>);
+
+xtextrender!(Card, <div class="card">
+   <h1>{{ self.title }}</h1>
+   {{ if !self.header.0.is_empty() {{ <header>{{ self.header }}</header> }} }}
+   {{ for c in self.children.iter() {{ {{ match c {{ CardChildren::Display(d) => {{ {{ d }} }} }} }} }} }}
+   <footer>{{ self.footer }}</footer>
+</div>);
+
+#[test]
+fn slot1() {
+   let user = "<ann>";
+   assert_eq!(
+      xhtml!(<!Card title="t">
+         <:footer><b>by {{ user }}</b></:footer>
+         <?>body</?>
+         <:header><i>head</i></:header>
+      </Card>),
+      "<div class=\"card\"><h1>t</h1><header><i>head</i></header>body<footer><b>by &lt;ann&gt;</b></footer></div>"
+   );
+}
+
+#[test]
+fn slot2() {
+   assert_eq!(
+      xtext!(<!Card title="t"/>),
+      "<div class=\"card\"><h1>t</h1><footer></footer></div>"
+   );
+   let c = Card::builder().footer(rdxl::xmarkup!(<p>f</p>)).build();
+   assert_eq!(c.footer, "<p>f</p>");
+   assert_eq!(c.header, Markup::default());
+}
+
+xtype!(<!Dialog title!:String>
+   <slot:actions/>
+</Dialog>);
+
+xrender!(Dialog, <dialog><h2>{{ self.title }}</h2>{{ self.actions }}</dialog>);
+
+#[test]
+fn slot3() {
+   assert_eq!(
+      xtext!(<!Dialog title="Quit?"><:actions><button>ok</button></:actions></Dialog>),
+      "<dialog><h2>Quit?</h2><button>ok</button></dialog>"
+   );
+}
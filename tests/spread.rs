@@ -0,0 +1,39 @@
+use rdxl::{xhtml,xtext};
+use std::collections::BTreeMap;
+
+#[test]
+fn spread1() {
+   let attrs = vec![("data-id", "3"), ("title", "a \"b\" & c")];
+   assert_eq!(
+      xtext!(<div class="x" {{..attrs}}>a</div>),
+      "<div class=\"x\" data-id=\"3\" title=\"a &quot;b&quot; &amp; c\">a</div>"
+   );
+}
+
+#[test]
+fn spread2() {
+   let mut attrs = BTreeMap::new();
+   attrs.insert("class".to_string(), "y".to_string());
+   attrs.insert("id".to_string(), "z".to_string());
+   assert_eq!(
+      xtext!(<div class="x" id="a" {{..&attrs}} id="b"/>),
+      "<div class=\"y\" id=\"b\"></div>"
+   );
+}
+
+#[test]
+fn spread3() {
+   let attrs = vec![("onclick=\"x\" y", 1), ("ok", 2)];
+   let hidden = Some(true);
+   let show = false;
+   assert_eq!(
+      xhtml!(<p {{..attrs}} hidden={{ hidden }} {{ if show {{ title="t" }} }}>b</p>),
+      "<p ok=\"2\" hidden>b</p>"
+   );
+}
+
+#[test]
+fn spread4() {
+   let attrs = [("a<b", "1"), ("a&amp;b", "2"), ("c", "3")];
+   assert_eq!(xhtml!(<p {{..attrs}}/>), "<p c=\"3\"></p>");
+}
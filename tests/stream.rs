@@ -0,0 +1,72 @@
+use rdxl::{xhtml,xhtml_to,xtext_to,xtype,xrender};
+use std::fmt;
+use std::io::{self, Write};
+
+xtype!(<!Row cell:String/>);
+xrender!(Row, <tr><td>{{ self.cell }}</td></tr>);
+
+struct Full(usize);
+impl Write for Full {
+   fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+      if buf.len() > self.0 {
+         return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+      }
+      self.0 -= buf.len();
+      Ok(buf.len())
+   }
+   fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+struct Fails;
+impl fmt::Write for Fails {
+   fn write_str(&mut self, _s: &str) -> fmt::Result { Err(fmt::Error) }
+}
+
+#[test]
+fn stream_fmt1() {
+   let mut s = String::new();
+   xhtml_to!(&mut s, <p>{{ "a<b" }}</p>).unwrap();
+   xhtml_to!(&mut s, <br/>).unwrap();
+   assert_eq!(s, "<p>a&lt;b</p><br/>");
+}
+
+#[test]
+fn stream_io1() {
+   let mut v: Vec<u8> = Vec::new();
+   xhtml_to!(&mut v, <table>
+      {{ for c in ["x", "y&z"].iter() {{
+         <!Row cell={{ c.to_string() }}/>
+      }} }}
+   </table>).unwrap();
+   let cells = ["x".to_string(), "y&z".to_string()];
+   assert_eq!(
+      String::from_utf8(v).unwrap(),
+      xhtml!(<table>
+         {{ for c in cells.iter() {{
+            <!Row cell={{ c.clone() }}/>
+         }} }}
+      </table>)
+   );
+}
+
+#[test]
+fn stream_io2() {
+   let mut out = io::BufWriter::new(Vec::new());
+   xtext_to!(&mut out, <p>{{ 1 }} {{ 2 }}</p>).unwrap();
+   assert_eq!(out.into_inner().unwrap(), b"<p>12</p>");
+}
+
+#[test]
+fn stream_error1() {
+   let e = xhtml_to!(Full(4), <div>{{ "overflow" }}</div>).unwrap_err();
+   assert_eq!(e.kind(), io::ErrorKind::WriteZero);
+   assert_eq!(xhtml_to!(Fails, <div/>), Err(fmt::Error));
+}
+
+#[test]
+fn stream_error2() {
+   let mut s = String::new();
+   assert!(fmt::write(&mut s, format_args!("{}", Row { cell: "c".to_string(), ..Default::default() })).is_ok());
+   assert_eq!(s, "<tr><td>c</td></tr>");
+   assert_eq!(xhtml_to!(Fails, <!Row cell={{ "c".to_string() }}/>), Err(fmt::Error));
+}
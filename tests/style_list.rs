@@ -0,0 +1,39 @@
+use rdxl::{xhtml,xtext};
+
+#[test]
+fn style_list1() {
+   let top = 10;
+   let shown = false;
+   assert_eq!(
+      xtext!(<div style=[position: "absolute", top: top, "z-index": 3, left: 0 if shown]/>),
+      "<div style=\"position:absolute;top:10;z-index:3\"></div>"
+   );
+}
+
+#[test]
+fn style_list2() {
+   let color: Option<String> = Some("red".to_string());
+   let width: Option<u32> = None;
+   assert_eq!(
+      xtext!(<p style=[color: color, width: width, font-size: "12px"]>a</p>),
+      "<p style=\"color:red;font-size:12px\">a</p>"
+   );
+   assert_eq!(color.as_deref(), Some("red"));
+}
+
+#[test]
+fn style_list3() {
+   let none: Option<&str> = None;
+   assert_eq!(xhtml!(<p style=[color: none] id="x">b</p>), "<p id=\"x\">b</p>");
+   let list = "blue";
+   assert_eq!(xhtml!(<p style=[color: list]/>), "<p style=\"color:blue\"></p>");
+}
+
+#[test]
+fn style_list4() {
+   let evil = "red;}\"<b>";
+   assert_eq!(
+      xtext!(<p style=[color: evil]/>),
+      "<p style=\"color:red\\3b \\7d \\22 \\3c b\\3e \"></p>"
+   );
+}
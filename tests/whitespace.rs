@@ -0,0 +1,34 @@
+use rdxl::{xhtml,xtext};
+
+#[test]
+fn whitespace1() {
+   assert_eq!(xhtml!(<p><a href="/">link</a> {{- "," }} next</p>),
+      "<p><a href=\"/\">link</a>, next</p>");
+}
+
+#[test]
+fn whitespace2() {
+   let xs = [1, 2];
+   assert_eq!(xhtml!(<ul>
+      {{- for x in xs.iter() {{ <li>{{ x }}</li> }} -}}
+   </ul>), "<ul><li>1</li><li>2</li></ul>");
+   assert_eq!(xhtml!(<ul>
+      {{ for x in xs.iter() {{ <li>{{ x }}</li> }} }}
+   </ul>), "<ul> <li>1</li> <li>2</li>  </ul>");
+}
+
+#[test]
+fn whitespace3() {
+   let n = 3;
+   assert_eq!(xhtml!(<p> {{- n -}} </p> {{+}} <p>x</p>), "<p>3</p> <p>x</p>");
+   assert_eq!(xtext!(<b>a</b>{{+}}<i>b</i>{{ if n > 2 {{ c }} }}{{+}}d), "<b>a</b> <i>b</i>c d");
+   assert_eq!(xhtml!({{ (-n) }}), "-3");
+}
+
+#[test]
+fn whitespace4() {
+   let x = 5;
+   assert_eq!(xhtml!(<b>{{ (-x) }}</b>), "<b>-5</b>");
+   assert_eq!(xhtml!(<p data-n={{ (-x) }}/>), "<p data-n=\"-5\"></p>");
+   assert_eq!(xhtml!(<i>a</i> {{ -x }} <i>b</i>), "<i>a</i>5 <i>b</i>");
+}
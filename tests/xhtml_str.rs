@@ -0,0 +1,41 @@
+use rdxl::xhtml_str;
+
+#[test]
+fn xhtml_str1() {
+   let name = "<Ann>";
+   assert_eq!(xhtml_str!(r#"<p>Don't {panic}, [[it's]] # // fine, {{ name }}!</p>"#),
+      "<p>Don't {panic}, [[it's]] # // fine, &lt;Ann&gt;!</p>");
+}
+
+#[test]
+fn xhtml_str2() {
+   let xs = [1, 2, 3];
+   assert_eq!(xhtml_str!(r#"<style>li { color: red }</style>
+<ul>{{ for x in xs.iter() {{
+  <li>{{ x }}</li>}} }}
+</ul>"#), "<style>li { color: red }</style>\n<ul>\n  <li>1</li>\n  <li>2</li>\n  <li>3</li>\n</ul>");
+}
+
+#[test]
+fn xhtml_str3() {
+   let n: Option<u32> = Some(3);
+   assert_eq!(xhtml_str!(r#"{{ match n {{
+      Some(n) if n > 2 => {{<b>{{ n }}</b>}},
+      _ => {{none}}
+   }} }}{{ let s = "}}"; }}{{ s }}"#), "<b>3</b>}}");
+}
+
+#[test]
+fn xhtml_str4() {
+   let href = r#"x" onclick='y'"#;
+   let active = true;
+   assert_eq!(xhtml_str!(r#"<a href="{{ href }}" class='{{ if active {{on}} }}'>{{ href }}</a>"#),
+      "<a href=\"x&quot; onclick=&#39;y&#39;\" class='on'>x\" onclick='y'</a>");
+}
+
+#[test]
+fn xhtml_str5() {
+   let xs = [(1, 'a'), (2, '}')];
+   assert_eq!(xhtml_str!(r#"<p>{{ xs[(xs.len() - 1)].1 }} ({{ [xs[0].0, 3].iter().sum::<i32>() }}]</p>"#),
+      "<p>} (4]</p>");
+}
@@ -0,0 +1,57 @@
+use rdxl::{xml,xhtml};
+
+#[test]
+fn xml1() {
+   assert_eq!(xml!(
+      <?xml version="1.0" encoding="utf-8"?>
+      <?xml-stylesheet type="text/xsl" href="style.xsl"?>
+      <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+         <use xlink:href="#dot"/>
+         <circle r="1"></circle>
+      </svg>
+   ), "<?xml version=\"1.0\" encoding=\"utf-8\"?> <?xml-stylesheet type=\"text/xsl\" href=\"style.xsl\"?> \
+        <svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"> \
+        <use xlink:href=\"#dot\"/> <circle r=\"1\"/> </svg>");
+   assert_eq!(xml!(<atom:link rel="self"/>), "<atom:link rel=\"self\"/>");
+   assert_eq!(xhtml!(<a:b c:d="e"></a:b>), "<a:b c:d=\"e\"></a:b>");
+}
+
+#[test]
+fn xml2() {
+   let text = "a < b\r\u{1}";
+   let attr = "\"x\"\ty\n";
+   assert_eq!(xml!(<p title={{ attr }}>{{ text }}</p>), "<p title=\"&quot;x&quot;&#9;y&#10;\">a &lt; b&#13;\u{fffd}</p>");
+   assert_eq!(xml!(<input checked disabled={{ true }} hidden={{ false }}/>), "<input checked=\"checked\" disabled=\"disabled\"/>");
+   assert_eq!(xhtml!(<input checked/>), "<input checked/>");
+}
+
+#[test]
+fn xml4() {
+   assert_eq!(xml!(<a t="x & y" n=1 c='"'>"Tom & Jerry < 3"</a>), "<a t=\"x &amp; y\" n=\"1\" c=\"&quot;\">Tom &amp; Jerry &lt; 3</a>");
+   assert_eq!(xhtml!(<a t="x & y">"Tom & Jerry"</a>), "<a t=\"x & y\">Tom & Jerry</a>");
+   let attrs = [("v", "x\ty\u{1}"), ("w", "<\"")];
+   assert_eq!(xml!(<e {{..attrs}}/>), "<e v=\"x&#9;y\u{fffd}\" w=\"&lt;&quot;\"/>");
+}
+
+#[test]
+fn xml3() {
+   let code = "if a]]>b { c && d }";
+   assert_eq!(xml!(<script><![CDATA["<" {{ code }}]]></script>),
+      "<script><![CDATA[<if a]]]]><![CDATA[>b { c && d }]]></script>");
+   assert_eq!(xml!(<e><![CDATA[]]></e>), "<e><![CDATA[]]></e>");
+   assert_eq!(xml!(<e><![CDATA["x]]>" "y"]]></e>), "<e><![CDATA[x]]]]><![CDATA[>y]]></e>");
+}
+
+struct Note(&'static str);
+impl Note {
+   fn to_t(&self) -> String { self.0.to_string() }
+}
+
+#[test]
+fn xml5() {
+   assert_eq!(xml!(<my-ns:foo-bar a-b:c-d="1"/>), "<my-ns:foo-bar a-b:c-d=\"1\"/>");
+   let note = Note("\"a\"\tb & c");
+   let tab = "\t";
+   assert_eq!(xml!(<e t=[[ note ]] u={{ if true {{ "x & y\n" }} }} v={{ match tab {{ t => {{ {{ t }} }} }} }}/>),
+      "<e t=\"&quot;a&quot;&#9;b &amp; c\" u=\"x &amp; y&#10; \" v=\"&#9; \"/>");
+}